@@ -0,0 +1,35 @@
+//! Throughput of the `Log2Map` array methods over a 4096-sample buffer.
+//!
+//! Run with `cargo bench --bench log2`, and again with `--features fast-math`
+//! to compare against the approximated path.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use normal_map::f32::Log2Map;
+
+const BUFFER_SIZE: usize = 4096;
+
+fn bench_log2(c: &mut Criterion) {
+    let map = Log2Map::new_frequency(20.0, 20480.0);
+
+    let normalized: Vec<f32> = (0..BUFFER_SIZE)
+        .map(|i| i as f32 / (BUFFER_SIZE - 1) as f32)
+        .collect();
+    let mut values = vec![0.0; BUFFER_SIZE];
+    map.denormalize_array(&normalized, &mut values);
+
+    let mut out = vec![0.0; BUFFER_SIZE];
+
+    let mut group = c.benchmark_group("log2");
+    group.throughput(Throughput::Elements(BUFFER_SIZE as u64));
+    group.bench_function("normalize_array", |b| {
+        b.iter(|| map.normalize_array(black_box(&values), &mut out))
+    });
+    group.bench_function("denormalize_array", |b| {
+        b.iter(|| map.denormalize_array(black_box(&normalized), &mut out))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_log2);
+criterion_main!(benches);
@@ -0,0 +1,74 @@
+//! Scalar and array throughput of every mapper, for both precisions.
+//!
+//! Run with `cargo bench --bench mappers`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const BUFFER_SIZES: [usize; 3] = [64, 512, 4096];
+
+macro_rules! bench_precision {
+    ($name:ident, $precision:ident) => {
+        fn $name(c: &mut Criterion) {
+            use normal_map::$precision::{NormalMap, Unit};
+
+            let maps = [
+                ("linear", NormalMap::linear(-50.0, 50.0, Unit::Generic)),
+                (
+                    "linear_db",
+                    NormalMap::linear(
+                        -90.0,
+                        6.0,
+                        Unit::Decibels {
+                            neg_infinity_clamp: Some(-90.0),
+                        },
+                    ),
+                ),
+                ("power", NormalMap::power(0.0, 1.0, 2.5, Unit::Generic)),
+                ("log2", NormalMap::log2_frequency(20.0, 20480.0)),
+                ("discrete", NormalMap::discrete(-100isize, 100isize)),
+            ];
+
+            for (map_name, map) in maps.iter() {
+                let mut group =
+                    c.benchmark_group(format!("{}/{}", stringify!($precision), map_name));
+
+                let value = map.denormalize(0.3);
+                group.bench_function("normalize", |b| b.iter(|| map.normalize(black_box(value))));
+                group.bench_function("denormalize", |b| {
+                    b.iter(|| map.denormalize(black_box(0.3)))
+                });
+
+                for &size in BUFFER_SIZES.iter() {
+                    let normalized: Vec<$precision> = (0..size)
+                        .map(|i| i as $precision / (size - 1) as $precision)
+                        .collect();
+                    let mut values = vec![0.0; size];
+                    map.denormalize_array(&normalized, &mut values);
+                    let mut out = vec![0.0; size];
+
+                    group.throughput(Throughput::Elements(size as u64));
+                    group.bench_with_input(
+                        BenchmarkId::new("normalize_array", size),
+                        &values,
+                        |b, values| b.iter(|| map.normalize_array(black_box(values), &mut out)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("denormalize_array", size),
+                        &normalized,
+                        |b, normalized| {
+                            b.iter(|| map.denormalize_array(black_box(normalized), &mut out))
+                        },
+                    );
+                }
+
+                group.finish();
+            }
+        }
+    };
+}
+
+bench_precision!(bench_f32, f32);
+bench_precision!(bench_f64, f64);
+
+criterion_group!(benches, bench_f32, bench_f64);
+criterion_main!(benches);
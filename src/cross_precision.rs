@@ -0,0 +1,23 @@
+//! Comparisons between maps of different precisions.
+//!
+//! These live outside of the `f32` and `f64` modules, which otherwise mirror
+//! each other.
+
+use crate::{f32, f64};
+
+impl f32::NormalMap {
+    /// Returns `true` if `self.denormalize(n)` is within `epsilon` of
+    /// `other.denormalize(n)` for `samples` evenly spaced normalized
+    /// positions `n`, where `other` is an `f64` map.
+    ///
+    /// This is useful for checking that the `f32` and `f64` definitions of the
+    /// same parameter agree. Please note that `epsilon` should allow for the
+    /// rounding error of `f32`.
+    pub fn approx_eq_f64(&self, other: &f64::NormalMap, samples: usize, epsilon: f64) -> bool {
+        (0..samples).all(|i| {
+            let normalized = f32::sample_position(i, samples);
+            let value = f64::from(self.denormalize(normalized));
+            (value - other.denormalize(f64::from(normalized))).abs() <= epsilon
+        })
+    }
+}
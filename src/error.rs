@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::fmt;
+
+/// An error returned when parsing a map or unit from a string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The mapper kind was not recognized.
+    UnknownMapper(String),
+    /// The unit was not recognized.
+    UnknownUnit(String),
+    /// A field could not be parsed as a number.
+    InvalidNumber(String),
+    /// The string had the wrong number of fields for its mapper kind.
+    WrongFieldCount,
+    /// The parsed arguments are not valid for the mapper.
+    InvalidArguments,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownMapper(s) => write!(f, "unknown mapper \"{}\"", s),
+            ParseError::UnknownUnit(s) => write!(f, "unknown unit \"{}\"", s),
+            ParseError::InvalidNumber(s) => write!(f, "invalid number \"{}\"", s),
+            ParseError::WrongFieldCount => write!(f, "wrong number of fields"),
+            ParseError::InvalidArguments => write!(f, "invalid arguments for mapper"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// An error returned when building a map from settings that don't combine
+/// meaningfully.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    /// The minimum of the range is greater than the maximum.
+    MinAboveMax,
+    /// A power curve has no exponent.
+    MissingExponent,
+    /// The exponent is not positive, or it or its reciprocal is not finite.
+    InvalidExponent,
+    /// An exponent was set for a curve that doesn't use one.
+    UnexpectedExponent,
+    /// A `log2` curve has a range that includes `0.0` or negative values.
+    NonPositiveLog2Range,
+    /// A `log2` curve uses decibel units. Decibels are already logarithmic,
+    /// so use a linear or power curve instead.
+    DecibelsOnLog2,
+    /// The unit is not supported by the kind of curve.
+    UnsupportedUnit,
+    /// The decibel clamp is above the minimum of the range.
+    ClampAboveMin,
+    /// The kind of curve is not supported by the builder.
+    UnsupportedKind,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MinAboveMax => write!(f, "minimum is greater than maximum"),
+            BuildError::MissingExponent => write!(f, "power curve has no exponent"),
+            BuildError::InvalidExponent => write!(f, "exponent is not positive and finite"),
+            BuildError::UnexpectedExponent => write!(f, "exponent set for a curve without one"),
+            BuildError::NonPositiveLog2Range => {
+                write!(f, "log2 curve has a range that is not strictly positive")
+            }
+            BuildError::DecibelsOnLog2 => {
+                write!(f, "decibel units can't be used with a log2 curve")
+            }
+            BuildError::UnsupportedUnit => write!(f, "unit is not supported by the curve"),
+            BuildError::ClampAboveMin => {
+                write!(f, "decibel clamp is above the minimum of the range")
+            }
+            BuildError::UnsupportedKind => write!(f, "kind of curve is not supported"),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// An error returned when an input and output slice differ in length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthMismatch {
+    /// The length of the input slice.
+    pub input_len: usize,
+    /// The length of the output slice.
+    pub output_len: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "input length {} does not match output length {}",
+            self.input_len, self.output_len
+        )
+    }
+}
+
+impl Error for LengthMismatch {}
+
+/// An error returned when the `neg_infinity_clamp` of `Unit::Decibels` is
+/// above the minimum of the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClampAboveMin;
+
+impl fmt::Display for ClampAboveMin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "decibel clamp is above the minimum of the range")
+    }
+}
+
+impl Error for ClampAboveMin {}
+
+/// An error returned when a value is not in the normalized range `[0.0, 1.0]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotNormalized;
+
+impl fmt::Display for NotNormalized {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "value is not in the normalized range [0.0, 1.0]")
+    }
+}
+
+impl Error for NotNormalized {}
+
+/// An error returned when samples that must be strictly increasing are not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotMonotonic {
+    /// The index of the first sample that is not greater than the one before it.
+    pub index: usize,
+}
+
+impl fmt::Display for NotMonotonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "sample {} is not greater than the sample before it",
+            self.index
+        )
+    }
+}
+
+impl Error for NotMonotonic {}
@@ -0,0 +1,68 @@
+//! Normal mapping of `half::f16` values, using `f32` as the internal unit.
+//!
+//! Values are upcast to `f32` before mapping and the results are downcast
+//! back to `f16`. Please note the precision limits of `f16`: it has about
+//! three significant decimal digits, so normalized values near `1.0` are
+//! spaced roughly `0.0005` apart, and raw values above `65504.0` overflow
+//! to infinity.
+
+use half::f16;
+
+use crate::f32;
+
+/// A mapper than maps a range of `f16` values to and from the normalized
+/// `f16` range `[0.0, 1.0]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalMap {
+    /// The `f32` map used internally
+    pub map: f32::NormalMap,
+}
+
+impl NormalMap {
+    /// Create a new `NormalMap` for `f16` values using the given `f32` map.
+    pub fn new(map: f32::NormalMap) -> Self {
+        Self { map }
+    }
+
+    /// Map an `f16` value to the normalized range `[0.0, 1.0]`.
+    pub fn normalize(&self, value: f16) -> f16 {
+        f16::from_f32(self.map.normalize(value.to_f32()))
+    }
+
+    /// Map an array of `f16` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[f16], out_normalized: &mut [f16]) {
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        let input = &in_values[..min_len];
+        let output = &mut out_normalized[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.normalize(input[i]);
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding `f16` value.
+    pub fn denormalize(&self, normalized: f16) -> f16 {
+        f16::from_f32(self.map.denormalize(normalized.to_f32()))
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f16` value.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[f16], out_values: &mut [f16]) {
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        let input = &in_normalized[..min_len];
+        let output = &mut out_values[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.denormalize(input[i]);
+        }
+    }
+}
+
+impl From<f32::NormalMap> for NormalMap {
+    fn from(map: f32::NormalMap) -> Self {
+        Self::new(map)
+    }
+}
@@ -2,17 +2,41 @@ use std::fmt::Debug;
 
 use super::Unit;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Base {
     Generic(Generic),
+    Frequency(Generic),
+    Percent(Generic),
+    /// `Unit::Generic` built in a `const` context.
+    GenericConst(GenericConst),
     DB(DB),
     DBClamped(DBClamped),
 }
 
+// A `GenericConst` is equal to the `Generic` it stands for, so a map built in
+// a `const` context compares equal to the same map built at runtime.
+impl PartialEq for Base {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Base::Generic(a), Base::Generic(b))
+            | (Base::Frequency(a), Base::Frequency(b))
+            | (Base::Percent(a), Base::Percent(b)) => a == b,
+            (Base::GenericConst(a), Base::GenericConst(b)) => a == b,
+            (Base::GenericConst(a), Base::Generic(b))
+            | (Base::Generic(b), Base::GenericConst(a)) => a.to_generic() == *b,
+            (Base::DB(a), Base::DB(b)) => a == b,
+            (Base::DBClamped(a), Base::DBClamped(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
 impl Base {
     pub fn new(min: f32, max: f32, unit: Unit) -> Self {
         match unit {
             Unit::Generic => Base::Generic(Generic::new(min, max)),
+            Unit::Frequency => Base::Frequency(Generic::new(min, max)),
+            Unit::Percent => Base::Percent(Generic::new(min, max)),
             Unit::Decibels { neg_infinity_clamp } => {
                 if let Some(clamp_db) = neg_infinity_clamp {
                     Base::DBClamped(DBClamped::new(min, max, clamp_db))
@@ -22,9 +46,60 @@ impl Base {
             }
         }
     }
+
+    /// The raw values at either end of the normalized range.
+    ///
+    /// For decibel units these are amplitudes, not decibels.
+    pub fn value_bounds(&self, min: f32, max: f32) -> (f32, f32) {
+        match self {
+            Base::Generic(_) | Base::Frequency(_) | Base::Percent(_) | Base::GenericConst(_) => {
+                (min, max)
+            }
+            Base::DB(_) => (db_to_coeff(min), db_to_coeff(max)),
+            Base::DBClamped(base) => (base.clamped_coeff(min), base.clamped_coeff(max)),
+        }
+    }
+
+    pub fn format_value(&self, value: f32, decimals: usize) -> String {
+        match self {
+            Base::Generic(_) | Base::GenericConst(_) => format!("{:.*}", decimals, value),
+            Base::Frequency(_) => format_frequency(value, decimals),
+            Base::Percent(_) => format!("{:.*}%", decimals, value),
+            Base::DB(_) => format_db(value, 0.0, decimals),
+            Base::DBClamped(base) => format_db(value, base.clamp_coeff, decimals),
+        }
+    }
+
+    pub fn unit(&self) -> Unit {
+        match self {
+            Base::Generic(_) | Base::GenericConst(_) => Unit::Generic,
+            Base::Frequency(_) => Unit::Frequency,
+            Base::Percent(_) => Unit::Percent,
+            Base::DB(_) => Unit::Decibels {
+                neg_infinity_clamp: None,
+            },
+            Base::DBClamped(base) => Unit::Decibels {
+                neg_infinity_clamp: Some(base.clamp_db),
+            },
+        }
+    }
+
+    pub fn clamp_db(&self) -> Option<f32> {
+        match self {
+            Base::DBClamped(base) => Some(base.clamp_db),
+            _ => None,
+        }
+    }
+
+    pub fn clamp_coeff(&self) -> Option<f32> {
+        match self {
+            Base::DBClamped(base) => Some(base.clamp_coeff),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Generic {
     min: f32,
     range: f32,
@@ -43,18 +118,40 @@ impl Generic {
         }
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     pub fn normalize(&self, value: f32) -> f32 {
         (value - self.min) * self.range_inv
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     pub fn denormalize(&self, normalized: f32) -> f32 {
         (normalized * self.range) + self.min
     }
 }
 
-#[derive(Debug)]
+/// The range of a `Generic` that was built in a `const fn`, where the range
+/// and its reciprocal can't be computed yet.
+///
+/// Use `to_generic` to get a `Generic` before mapping values, once per array
+/// rather than once per value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericConst {
+    min: f32,
+    max: f32,
+}
+
+impl GenericConst {
+    pub const fn new(min: f32, max: f32) -> Self {
+        Self { min, max }
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub fn to_generic(&self) -> Generic {
+        Generic::new(self.min, self.max)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct DB {
     min: f32,
     range: f32,
@@ -73,18 +170,18 @@ impl DB {
         }
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     pub fn normalize(&self, value: f32) -> f32 {
         (coeff_to_db(value) - self.min) * self.range_inv
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     pub fn denormalize(&self, normalized: f32) -> f32 {
         db_to_coeff((normalized * self.range) + self.min)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DBClamped {
     min: f32,
     range: f32,
@@ -107,7 +204,15 @@ impl DBClamped {
         }
     }
 
-    #[inline(always)]
+    fn clamped_coeff(&self, db: f32) -> f32 {
+        if db <= self.clamp_db {
+            0.0
+        } else {
+            db_to_coeff(db)
+        }
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     pub fn normalize(&self, value: f32) -> f32 {
         let db = if value <= self.clamp_coeff {
             self.clamp_db
@@ -118,7 +223,7 @@ impl DBClamped {
         (db - self.min) * self.range_inv
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     pub fn denormalize(&self, normalized: f32) -> f32 {
         let value = (normalized * self.range) + self.min;
 
@@ -130,12 +235,28 @@ impl DBClamped {
     }
 }
 
-#[inline(always)]
-fn db_to_coeff(db: f32) -> f32 {
+pub fn format_frequency(value: f32, decimals: usize) -> String {
+    if value.abs() >= 1000.0 {
+        format!("{:.*} kHz", decimals, value * 0.001)
+    } else {
+        format!("{:.*} Hz", decimals, value)
+    }
+}
+
+fn format_db(value: f32, neg_infinity_coeff: f32, decimals: usize) -> String {
+    if value <= neg_infinity_coeff {
+        String::from("-inf dB")
+    } else {
+        format!("{:.*} dB", decimals, coeff_to_db(value))
+    }
+}
+
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+pub fn db_to_coeff(db: f32) -> f32 {
     10.0f32.powf(0.05 * db)
 }
 
-#[inline(always)]
-fn coeff_to_db(coeff: f32) -> f32 {
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+pub fn coeff_to_db(coeff: f32) -> f32 {
     20.0 * coeff.log(10.0)
 }
@@ -0,0 +1,52 @@
+//! Zero-sized unit markers for [`TypedNormalMap`](super::TypedNormalMap).
+//!
+//! These let the type system tell apart maps of different units, such as a
+//! `TypedNormalMap<Decibels>` and a `TypedNormalMap<Generic>`.
+
+use super::Unit;
+
+/// A compile-time marker for a type of unit
+pub trait UnitMarker {
+    /// Returns `true` if the runtime `unit` is of the type this marker represents.
+    fn matches(unit: Unit) -> bool;
+}
+
+/// Marker for `Unit::Generic`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Generic;
+
+/// Marker for `Unit::Frequency`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frequency;
+
+/// Marker for `Unit::Percent`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Percent;
+
+/// Marker for `Unit::Decibels`, with or without a `neg_infinity_clamp`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decibels;
+
+impl UnitMarker for Generic {
+    fn matches(unit: Unit) -> bool {
+        unit == Unit::Generic
+    }
+}
+
+impl UnitMarker for Frequency {
+    fn matches(unit: Unit) -> bool {
+        unit == Unit::Frequency
+    }
+}
+
+impl UnitMarker for Percent {
+    fn matches(unit: Unit) -> bool {
+        unit == Unit::Percent
+    }
+}
+
+impl UnitMarker for Decibels {
+    fn matches(unit: Unit) -> bool {
+        matches!(unit, Unit::Decibels { .. })
+    }
+}
@@ -1,18 +1,57 @@
 //! Normal mapping using `f32` as the internal unit.
 
+#[cfg(feature = "approx")]
+mod approx_eq;
+mod bilinear;
+mod builder;
+mod descriptor;
 mod discrete;
 mod linear;
 mod linear_base;
 mod log2;
+mod normalized;
+mod note;
+mod piecewise;
 mod power;
+mod smoothstep;
+mod stepped;
+mod typed;
+mod wrapping;
+mod xy;
 
-pub use discrete::DiscreteMap;
+pub mod marker;
+
+pub use bilinear::BilinearMap;
+pub use builder::NormalMapBuilder;
+pub use discrete::{DiscreteMap, RoundingMode};
 pub use linear::LinearMap;
 pub use log2::Log2Map;
+pub use normalized::Normalized;
+pub use note::NoteMap;
+pub use piecewise::PiecewiseMap;
 pub use power::PowerMap;
+pub use smoothstep::{SmoothOrder, SmoothstepMap};
+pub use stepped::SteppedMap;
+pub use typed::TypedNormalMap;
+pub use wrapping::WrappingMap;
+pub use xy::XYMap;
+
+use crate::{ClampAboveMin, LengthMismatch, NotMonotonic, ParseError};
+
+/// The number of values each thread processes at a time in the parallel
+/// array methods. Shorter arrays are processed serially.
+#[cfg(feature = "rayon")]
+const PAR_CHUNK_LEN: usize = 16384;
+
+/// The number of segments `NormalMap::low_resolution_regions` checks, one
+/// per normalized percent.
+const RESOLUTION_SEGMENTS: usize = 100;
+
+/// The maximum of a 14-bit integer.
+const U14_MAX: f32 = 16383.0;
 
 /// The type of mapping to use
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Mapper {
     /// Linear mapping
     ///
@@ -32,32 +71,167 @@ pub enum Mapper {
     /// A supplied enum may be used as well as long
     /// as it implements `From<isize> + Into<isize> + Copy + Clone`.
     Discrete(DiscreteMap),
+    /// Two-segment linear mapping with a breakpoint
+    Bilinear(BilinearMap),
+    /// Linear mapping where values snap to evenly spaced steps
+    Stepped(SteppedMap),
+    /// S-shaped mapping using the smoothstep family of curves
+    Smoothstep(SmoothstepMap),
+    /// Mapping over a range of MIDI notes, where values snap to the
+    /// frequencies of the nearest equal-tempered note
+    Note(NoteMap),
+    /// Linear mapping for cyclic values, where values outside of the range
+    /// wrap around
+    Wrapping(WrappingMap),
+    /// Mapping that linearly interpolates between raw values sampled at
+    /// evenly spaced normalized positions
+    Piecewise(PiecewiseMap),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 /// The unit to use
 pub enum Unit {
     /// Generic units
     Generic,
+    /// Frequency units in Hz.
+    ///
+    /// This does not change the mapping itself, but values are formatted
+    /// in Hz, or kHz at and above 1000 Hz. It is intended to be used with
+    /// logarithmic mapping (see `NormalMap::log2_frequency`).
+    Frequency,
+    /// Percentage units.
+    ///
+    /// This does not change the mapping itself, but values are formatted
+    /// with a `%` suffix (see `NormalMap::percent`).
+    Percent,
     /// Decibel units.
     ///
     /// Please note that values in and out of the mapper are raw amplitudes, not decibels.
     ///
     /// * `neg_infinity_clamp`: The point at which any values less than
-    /// or equal to this value (e.g. `Some(-90.0)` for -90 dB) are clampled to negative
-    /// infinity (silence). Set this to `None` for no clamping.
+    ///   or equal to this value (e.g. `Some(-90.0)` for -90 dB) are clampled to negative
+    ///   infinity (silence). Set this to `None` for no clamping.
+    ///
+    /// If the `neg_infinity_clamp` is above the minimum of the range, then the
+    /// whole bottom of the range up to the clamp is silence: those normalized
+    /// values denormalize to `0.0`. Use [`Unit::decibels_clamped`] to rule this out.
     Decibels { neg_infinity_clamp: Option<f32> },
 }
 
+impl Unit {
+    /// Create `Unit::Decibels` with a `neg_infinity_clamp`, checking that the
+    /// clamp is not above the minimum of the range.
+    ///
+    /// # Arguments
+    ///
+    /// * neg_infinity_clamp - the decibels at or below which values are silence
+    /// * min_db - the minimum of the range of the map this unit is for
+    pub fn decibels_clamped(neg_infinity_clamp: f32, min_db: f32) -> Result<Self, ClampAboveMin> {
+        if neg_infinity_clamp > min_db {
+            return Err(ClampAboveMin);
+        }
+
+        Ok(Unit::Decibels {
+            neg_infinity_clamp: Some(neg_infinity_clamp),
+        })
+    }
+}
+
+/// The spacing of the round values returned by [`NormalMap::nice_tick_normals`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickSpacing {
+    /// Powers of ten, such as `1, 10, 100, ...`
+    Decade,
+    /// The 1-2-5 series, such as `1, 2, 5, 10, 20, 50, ...`
+    OneTwoFive,
+}
+
+/// The kind of curve a mapper uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveKind {
+    /// Linear mapping
+    Linear,
+    /// Exponential mapping
+    Power,
+    /// Logarithmic mapping using `log2`
+    Log2,
+    /// Discrete `isize` integer mapping
+    Discrete,
+    /// Two-segment linear mapping with a breakpoint
+    Bilinear,
+    /// Linear mapping where values snap to evenly spaced steps
+    Stepped,
+    /// S-shaped mapping using the smoothstep family of curves
+    Smoothstep,
+    /// Mapping over a range of MIDI notes
+    Note,
+    /// Linear mapping for cyclic values
+    Wrapping,
+    /// Interpolation between sampled raw values
+    Piecewise,
+}
+
+/// A plain description of the curve of a `NormalMap`, returned by
+/// [`NormalMap::describe`].
+///
+/// This is decoupled from the internal mapper types, which makes it suitable
+/// for sending across an RPC boundary or displaying in a debug inspector.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveDescriptor {
+    /// The kind of curve
+    pub kind: CurveKind,
+    /// The minimum of the range
+    ///
+    /// For `Unit::Decibels`, this is in decibels.
+    pub min: f32,
+    /// The maximum of the range
+    ///
+    /// For `Unit::Decibels`, this is in decibels.
+    pub max: f32,
+    /// The exponent, if the curve has one
+    pub exponent: Option<f32>,
+    /// The type of unit
+    pub unit: Unit,
+}
+
 /// A mapper than maps a range of values to and from the normalized
 /// `f32` range `[0.0, 1.0]`.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct NormalMap {
     /// The current mapper in use
     pub mapper: Mapper,
+    margin: f32,
+    margin_scale: f32,
+    margin_scale_inv: f32,
+    dead_zone: f32,
+    dead_zone_scale: f32,
+    dead_zone_scale_inv: f32,
 }
 
 impl NormalMap {
+    /// A `NormalMap` for which `normalize` and `denormalize` are the
+    /// identity on `[0.0, 1.0]`.
+    ///
+    /// This is equivalent to `NormalMap::linear(0.0, 1.0, Unit::Generic)`, and
+    /// is useful as a default in `const` and `static` contexts.
+    pub const IDENTITY: NormalMap = NormalMap::linear_const(0.0, 1.0);
+
+    /// Create a new `NormalMap` from a mapper, such as one built directly
+    /// with [`PowerMap::new`].
+    ///
+    /// Any concrete map type can be converted into a `Mapper` with `into()`.
+    pub const fn from_mapper(mapper: Mapper) -> Self {
+        Self {
+            mapper,
+            margin: 0.0,
+            margin_scale: 1.0,
+            margin_scale_inv: 1.0,
+            dead_zone: 0.0,
+            dead_zone_scale: 1.0,
+            dead_zone_scale_inv: 1.0,
+        }
+    }
+
     /// Create a new `NormalMap` with linear mapping.
     ///
     /// Please note if you use `Unit::Decibels`, then the decibels
@@ -68,10 +242,96 @@ impl NormalMap {
     /// * min - the minimum of the range
     /// * max - the maximum of the range
     /// * unit - the type of unit
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
     pub fn linear(min: f32, max: f32, unit: Unit) -> Self {
-        Self {
-            mapper: Mapper::Lin(LinearMap::new(min, max, unit)),
-        }
+        Self::from_mapper(Mapper::Lin(LinearMap::new(min, max, unit)))
+    }
+
+    /// Create a new `NormalMap` with linear mapping and `Unit::Generic`.
+    ///
+    /// Unlike [`NormalMap::linear`], this can be used in a `const` context,
+    /// such as when defining parameters in a `static` table. It does not check
+    /// that `min <= max`.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    pub const fn linear_const(min: f32, max: f32) -> Self {
+        Self::from_mapper(Mapper::Lin(LinearMap::new_const(min, max)))
+    }
+
+    /// Create a new `NormalMap` for a fader that goes from silence up to `max_db`.
+    ///
+    /// The decibels in `[floor_db, max_db]` are linearly mapped, and `floor_db`
+    /// is used as the `neg_infinity_clamp`. This means that `denormalize(0.0)`
+    /// returns an amplitude of exactly `0.0`, and any amplitude at or below
+    /// `floor_db` normalizes to `0.0`.
+    ///
+    /// Values in and out of the mapper are raw amplitudes.
+    ///
+    /// # Arguments
+    ///
+    /// * max_db - the maximum of the range in decibels
+    /// * floor_db - the decibels at the bottom of the throw, treated as silence
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `floor_db >= max_db`.
+    pub fn fader_db(max_db: f32, floor_db: f32) -> Self {
+        assert!(floor_db < max_db);
+
+        Self::linear(
+            floor_db,
+            max_db,
+            Unit::Decibels {
+                neg_infinity_clamp: Some(floor_db),
+            },
+        )
+    }
+
+    /// Create a new `NormalMap` for a standard mixer channel fader, where unity
+    /// gain (0 dB) sits at `0.75` and the top of the throw is +6 dB.
+    ///
+    /// The taper is a `Mapper::Pow` over the decibel range `[-60.0, 6.0]`, with
+    /// `-60.0` dB as the `neg_infinity_clamp` so the bottom of the throw is
+    /// silence. The exponent is `ln(60 / 66) / ln(0.75)` (about `0.33`), which
+    /// places 0 dB exactly at `0.75` and `-7.5` dB near the middle.
+    ///
+    /// Values in and out of the mapper are raw amplitudes.
+    pub fn mixer_fader() -> Self {
+        let max_db: f32 = 6.0;
+        let floor_db: f32 = -60.0;
+        let unity_normal: f32 = 0.75;
+
+        // Solve `unity_normal^exponent = (0 dB - floor_db) / (max_db - floor_db)`.
+        let exponent = (-floor_db / (max_db - floor_db)).ln() / unity_normal.ln();
+
+        Self::power(
+            floor_db,
+            max_db,
+            exponent,
+            Unit::Decibels {
+                neg_infinity_clamp: Some(floor_db),
+            },
+        )
+    }
+
+    /// Create a new `NormalMap` with linear mapping over the range
+    /// `[0.0, 100.0]` and `Unit::Percent`.
+    pub fn percent() -> Self {
+        Self::linear(0.0, 100.0, Unit::Percent)
+    }
+
+    /// Create a new `NormalMap` with linear mapping over the range
+    /// `[0.0, 1.0]`, for percentages that are stored as a fraction.
+    ///
+    /// This uses `Unit::Generic`, since the values are not in percent.
+    pub fn percent_normalized() -> Self {
+        Self::linear(0.0, 1.0, Unit::Generic)
     }
 
     /// Create a new `NormalMap` with an exponential mapping where the
@@ -90,10 +350,10 @@ impl NormalMap {
     /// # Panics
     ///
     /// * Panics when `exponent = 0.0`.
+    /// * Panics when `exponent` or `1.0 / exponent` is not finite.
+    /// * Panics when `min > max`.
     pub fn power(min: f32, max: f32, exponent: f32, unit: Unit) -> Self {
-        Self {
-            mapper: Mapper::Pow(PowerMap::new(min, max, exponent, unit)),
-        }
+        Self::from_mapper(Mapper::Pow(PowerMap::new(min, max, exponent, unit)))
     }
 
     /// Create a new `NormalMap` with a logarithmic mapping using `log2`.
@@ -107,10 +367,47 @@ impl NormalMap {
     /// # Panics
     ///
     /// * Panics when either `min` or `max` <= 0.0.
+    /// * Panics when `min > max`.
     pub fn log2(min: f32, max: f32) -> Self {
-        Self {
-            mapper: Mapper::Log2(Log2Map::new(min, max)),
-        }
+        Self::from_mapper(Mapper::Log2(Log2Map::new(min, max)))
+    }
+
+    /// Create a new `NormalMap` with a logarithmic mapping using `log2`,
+    /// using `Unit::Frequency` for formatting values.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range in Hz, must be > 0.0
+    /// * max - the maximum of the range in Hz, must be > 0.0
+    ///
+    /// # Panics
+    ///
+    /// * Panics when either `min` or `max` <= 0.0.
+    /// * Panics when `min > max`.
+    pub fn log2_frequency(min: f32, max: f32) -> Self {
+        Self::from_mapper(Mapper::Log2(Log2Map::new_frequency(min, max)))
+    }
+
+    /// Create a new `NormalMap` with a logarithmic pitch mapping spanning
+    /// `semitone_range` semitones above and below `center_hz`.
+    ///
+    /// # Arguments
+    ///
+    /// * center_hz - the frequency at the center of the range, must be > 0.0
+    /// * semitone_range - the number of semitones on either side of the center, must be > 0.0
+    ///
+    /// # Panics
+    ///
+    /// * Panics when either `center_hz` or `semitone_range` <= 0.0.
+    pub fn pitch_semitones(center_hz: f32, semitone_range: f32) -> Self {
+        assert!(semitone_range > 0.0);
+
+        let octaves = semitone_range / 12.0;
+
+        Self::log2(
+            center_hz * 2.0f32.powf(-octaves),
+            center_hz * 2.0f32.powf(octaves),
+        )
     }
 
     /// Create a new `NormalMap` with a discrete `isize` integer range.
@@ -122,56 +419,1975 @@ impl NormalMap {
     ///
     /// * min - the minimum of the range
     /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
     pub fn discrete<T>(min: T, max: T) -> Self
     where
         T: From<isize> + Into<isize> + Copy + Clone,
     {
-        Self {
-            mapper: Mapper::Discrete(DiscreteMap::new(min, max)),
+        Self::from_mapper(Mapper::Discrete(DiscreteMap::new(min, max)))
+    }
+
+    /// Create a new `NormalMap` with a discrete `isize` integer range, where
+    /// `min` maps to `1.0` and `max` maps to `0.0`.
+    ///
+    /// A supplied enum may be used as well as long
+    /// as it implements `From<isize> + Into<isize> + Copy + Clone`.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    pub fn discrete_reversed<T>(min: T, max: T) -> Self
+    where
+        T: From<isize> + Into<isize> + Copy + Clone,
+    {
+        Self::from_mapper(Mapper::Discrete(DiscreteMap::new_reversed(min, max)))
+    }
+
+    /// Create a new `NormalMap` with a discrete `isize` integer mapping where
+    /// each inclusive `(start, end)` range of values behaves as one step.
+    ///
+    /// See [`DiscreteMap::grouped`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * groups - the inclusive `(start, end)` ranges, in ascending order
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `groups` is empty.
+    /// * Panics when `start > end` for any group.
+    /// * Panics when the groups overlap or are not in ascending order.
+    pub fn discrete_grouped(groups: &[(isize, isize)]) -> Self {
+        Self::from_mapper(Mapper::Discrete(DiscreteMap::grouped(groups)))
+    }
+
+    /// Create a new `NormalMap` with a two-segment linear mapping, where
+    /// `center_raw` maps to `center_norm` and each half is linear.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * center_raw - the value at the breakpoint
+    /// * center_norm - the normalized position of the breakpoint
+    /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min < center_raw < max` does not hold.
+    /// * Panics when `0.0 < center_norm < 1.0` does not hold.
+    pub fn bilinear(min: f32, center_raw: f32, center_norm: f32, max: f32) -> Self {
+        Self::from_mapper(Mapper::Bilinear(BilinearMap::new(
+            min,
+            center_raw,
+            center_norm,
+            max,
+        )))
+    }
+
+    /// Create a new `NormalMap` with linear mapping where values snap to
+    /// evenly spaced steps above `min`.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    /// * step - the distance between two steps, must be > 0.0
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `step <= 0.0` or `step` is not finite.
+    /// * Panics when `min > max`.
+    pub fn stepped(min: f32, max: f32, step: f32) -> Self {
+        Self::from_mapper(Mapper::Stepped(SteppedMap::new(min, max, step)))
+    }
+
+    /// Create a new `NormalMap` with an S-shaped mapping using the
+    /// smoothstep curve of the given order.
+    ///
+    /// See [`SmoothstepMap::new_order`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    /// * order - the order of the curve
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    pub fn smoothstep(min: f32, max: f32, order: SmoothOrder) -> Self {
+        Self::from_mapper(Mapper::Smoothstep(SmoothstepMap::new_order(
+            min, max, order,
+        )))
+    }
+
+    /// Create a new `NormalMap` over a range of MIDI notes, where values snap
+    /// to the frequencies (in Hz) of the nearest equal-tempered note.
+    ///
+    /// # Arguments
+    ///
+    /// * ref_a4_hz - the frequency of A4 in Hz (usually `440.0`), must be > 0.0
+    /// * min_note - the lowest MIDI note of the range
+    /// * max_note - the highest MIDI note of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `ref_a4_hz <= 0.0`.
+    /// * Panics when `min_note > max_note`.
+    pub fn note(ref_a4_hz: f32, min_note: i32, max_note: i32) -> Self {
+        Self::from_mapper(Mapper::Note(NoteMap::new(ref_a4_hz, min_note, max_note)))
+    }
+
+    /// Create a new `NormalMap` with linear mapping for cyclic values, such
+    /// as a phase in degrees.
+    ///
+    /// Values outside of the range wrap around instead of being clamped, and
+    /// both `0.0` and `1.0` denormalize to `min`.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the start of the cycle
+    /// * max - the end of the cycle, which is the same point as `min`
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    pub fn wrapping(min: f32, max: f32) -> Self {
+        Self::from_mapper(Mapper::Wrapping(WrappingMap::new(min, max)))
+    }
+
+    /// Create a new `NormalMap` that linearly interpolates between raw values
+    /// sampled at evenly spaced normalized positions, such as the measured
+    /// response curve of a hardware control.
+    ///
+    /// See [`PiecewiseMap::from_samples`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * raw_values - the raw value at each normalized position
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error when the samples are not strictly increasing.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when there are fewer than 2 samples.
+    pub fn piecewise(raw_values: &[f32]) -> Result<Self, NotMonotonic> {
+        Ok(Self::from_mapper(Mapper::Piecewise(
+            PiecewiseMap::from_samples(raw_values)?,
+        )))
+    }
+
+    /// Parse a `NormalMap` from a compact descriptor string.
+    ///
+    /// Fields are separated by `:`, and whitespace around each field is ignored.
+    ///
+    /// * `lin:<min>:<max>[:<unit>]`
+    /// * `pow:<min>:<max>:<exponent>[:<unit>]`
+    /// * `log2:<min>:<max>`
+    /// * `discrete:<min>:<max>`
+    /// * `group:<start>:<end>[:<start>:<end>...]`
+    /// * `bilin:<min>:<center_raw>:<center_norm>:<max>`
+    /// * `step:<min>:<max>:<step>`
+    /// * `smooth:<min>:<max>[:quintic]`
+    /// * `note:<ref_a4_hz>:<min_note>:<max_note>`
+    /// * `wrap:<min>:<max>`
+    /// * `piece:<value>:<value>[:<value>...]`
+    ///
+    /// where `<unit>` is `generic` (the default), `db`, or `db:<neg_infinity_clamp>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use normal_map::f32::NormalMap;
+    /// let normal_map = NormalMap::from_descriptor("lin:-50:50:db:-90").unwrap();
+    ///
+    /// assert_eq!(normal_map.to_descriptor(), "lin:-50:50:db:-90");
+    /// ```
+    pub fn from_descriptor(s: &str) -> Result<Self, ParseError> {
+        Ok(Self::from_mapper(descriptor::from_descriptor(s)?))
+    }
+
+    /// Serialize this map to a compact descriptor string that can be parsed
+    /// back with [`NormalMap::from_descriptor`].
+    ///
+    /// The margin and dead zone set with [`NormalMap::with_margin`] and
+    /// [`NormalMap::with_dead_zone`] are not included.
+    pub fn to_descriptor(&self) -> String {
+        descriptor::to_descriptor(&self.mapper)
+    }
+
+    /// Describe the curve of the current mapper.
+    ///
+    /// The margin and dead zone set with [`NormalMap::with_margin`] and
+    /// [`NormalMap::with_dead_zone`] are not included.
+    pub fn describe(&self) -> CurveDescriptor {
+        match &self.mapper {
+            Mapper::Lin(mapper) => CurveDescriptor {
+                kind: CurveKind::Linear,
+                min: mapper.min(),
+                max: mapper.max(),
+                exponent: None,
+                unit: mapper.unit(),
+            },
+            Mapper::Pow(mapper) => CurveDescriptor {
+                kind: CurveKind::Power,
+                min: mapper.min(),
+                max: mapper.max(),
+                exponent: Some(mapper.exponent()),
+                unit: mapper.unit(),
+            },
+            Mapper::Log2(mapper) => CurveDescriptor {
+                kind: CurveKind::Log2,
+                min: mapper.min(),
+                max: mapper.max(),
+                exponent: None,
+                unit: mapper.unit(),
+            },
+            Mapper::Discrete(mapper) => CurveDescriptor {
+                kind: CurveKind::Discrete,
+                min: mapper.min() as f32,
+                max: mapper.max() as f32,
+                exponent: None,
+                unit: Unit::Generic,
+            },
+            Mapper::Bilinear(mapper) => CurveDescriptor {
+                kind: CurveKind::Bilinear,
+                min: mapper.min(),
+                max: mapper.max(),
+                exponent: None,
+                unit: Unit::Generic,
+            },
+            Mapper::Stepped(mapper) => CurveDescriptor {
+                kind: CurveKind::Stepped,
+                min: mapper.min(),
+                max: mapper.max(),
+                exponent: None,
+                unit: Unit::Generic,
+            },
+            Mapper::Smoothstep(mapper) => CurveDescriptor {
+                kind: CurveKind::Smoothstep,
+                min: mapper.min(),
+                max: mapper.max(),
+                exponent: None,
+                unit: Unit::Generic,
+            },
+            Mapper::Note(mapper) => CurveDescriptor {
+                kind: CurveKind::Note,
+                min: mapper.min(),
+                max: mapper.max(),
+                exponent: None,
+                unit: Unit::Frequency,
+            },
+            Mapper::Wrapping(mapper) => CurveDescriptor {
+                kind: CurveKind::Wrapping,
+                min: mapper.min(),
+                max: mapper.max(),
+                exponent: None,
+                unit: Unit::Generic,
+            },
+            Mapper::Piecewise(mapper) => CurveDescriptor {
+                kind: CurveKind::Piecewise,
+                min: mapper.min(),
+                max: mapper.max(),
+                exponent: None,
+                unit: Unit::Generic,
+            },
         }
     }
 
-    /// Map an `f32` value to the normalized range `[0.0, 1.0]`.
-    pub fn normalize(&self, value: f32) -> f32 {
+    /// The `LinearMap` in use, or `None` if the mapper is not `Mapper::Lin`.
+    pub fn as_linear(&self) -> Option<&LinearMap> {
         match &self.mapper {
-            Mapper::Lin(mapper) => mapper.normalize(value),
-            Mapper::Pow(mapper) => mapper.normalize(value),
-            Mapper::Log2(mapper) => mapper.normalize(value),
-            Mapper::Discrete(mapper) => mapper.normalize_float(value),
+            Mapper::Lin(mapper) => Some(mapper),
+            _ => None,
         }
     }
 
-    /// Map an array of `f32` values to the normalized range `[0.0, 1.0]`.
-    ///
-    /// Values will be processed up to the length of the shortest array.
-    pub fn normalize_array(&self, in_values: &[f32], out_normalized: &mut [f32]) {
+    /// The `PowerMap` in use, or `None` if the mapper is not `Mapper::Pow`.
+    pub fn as_power(&self) -> Option<&PowerMap> {
         match &self.mapper {
-            Mapper::Lin(mapper) => mapper.normalize_array(in_values, out_normalized),
-            Mapper::Pow(mapper) => mapper.normalize_array(in_values, out_normalized),
-            Mapper::Log2(mapper) => mapper.normalize_array(in_values, out_normalized),
-            Mapper::Discrete(mapper) => mapper.normalize_array_float(in_values, out_normalized),
+            Mapper::Pow(mapper) => Some(mapper),
+            _ => None,
         }
     }
 
-    /// Un-map a normalized value to the corresponding `f32` value.
-    pub fn denormalize(&self, normalized: f32) -> f32 {
+    /// The `Log2Map` in use, or `None` if the mapper is not `Mapper::Log2`.
+    pub fn as_log2(&self) -> Option<&Log2Map> {
         match &self.mapper {
-            Mapper::Lin(mapper) => mapper.denormalize(normalized),
-            Mapper::Pow(mapper) => mapper.denormalize(normalized),
-            Mapper::Log2(mapper) => mapper.denormalize(normalized),
-            Mapper::Discrete(mapper) => mapper.denormalize_float(normalized),
+            Mapper::Log2(mapper) => Some(mapper),
+            _ => None,
         }
     }
 
-    /// Un-map an array of normalized values to the corresponding `f32` value.
-    ///
-    /// Values will be processed up to the length of the shortest array.
-    pub fn denormalize_array(&self, in_normalized: &[f32], out_values: &mut [f32]) {
+    /// The `DiscreteMap` in use, or `None` if the mapper is not `Mapper::Discrete`.
+    pub fn as_discrete(&self) -> Option<&DiscreteMap> {
         match &self.mapper {
-            Mapper::Lin(mapper) => mapper.denormalize_array(in_normalized, out_values),
-            Mapper::Pow(mapper) => mapper.denormalize_array(in_normalized, out_values),
-            Mapper::Log2(mapper) => mapper.denormalize_array(in_normalized, out_values),
-            Mapper::Discrete(mapper) => mapper.denormalize_array_float(in_normalized, out_values),
+            Mapper::Discrete(mapper) => Some(mapper),
+            _ => None,
+        }
+    }
+
+    /// The `BilinearMap` in use, or `None` if the mapper is not `Mapper::Bilinear`.
+    pub fn as_bilinear(&self) -> Option<&BilinearMap> {
+        match &self.mapper {
+            Mapper::Bilinear(mapper) => Some(mapper),
+            _ => None,
+        }
+    }
+
+    /// The `SteppedMap` in use, or `None` if the mapper is not `Mapper::Stepped`.
+    pub fn as_stepped(&self) -> Option<&SteppedMap> {
+        match &self.mapper {
+            Mapper::Stepped(mapper) => Some(mapper),
+            _ => None,
+        }
+    }
+
+    /// The `SmoothstepMap` in use, or `None` if the mapper is not `Mapper::Smoothstep`.
+    pub fn as_smoothstep(&self) -> Option<&SmoothstepMap> {
+        match &self.mapper {
+            Mapper::Smoothstep(mapper) => Some(mapper),
+            _ => None,
+        }
+    }
+
+    /// The `NoteMap` in use, or `None` if the mapper is not `Mapper::Note`.
+    pub fn as_note(&self) -> Option<&NoteMap> {
+        match &self.mapper {
+            Mapper::Note(mapper) => Some(mapper),
+            _ => None,
+        }
+    }
+
+    /// The `WrappingMap` in use, or `None` if the mapper is not `Mapper::Wrapping`.
+    pub fn as_wrapping(&self) -> Option<&WrappingMap> {
+        match &self.mapper {
+            Mapper::Wrapping(mapper) => Some(mapper),
+            _ => None,
+        }
+    }
+
+    /// The `PiecewiseMap` in use, or `None` if the mapper is not `Mapper::Piecewise`.
+    pub fn as_piecewise(&self) -> Option<&PiecewiseMap> {
+        match &self.mapper {
+            Mapper::Piecewise(mapper) => Some(mapper),
+            _ => None,
+        }
+    }
+
+    /// Create a copy of this `NormalMap` whose usable range is inset by
+    /// `margin` on both ends, so that the endpoints are never reached.
+    ///
+    /// The margin is in normalized units. For example with a margin of `0.01`,
+    /// `denormalize(0.0)` returns the value this map returns at `0.01`, and
+    /// `denormalize(1.0)` returns the value this map returns at `0.99`. Any
+    /// existing margin is replaced.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `margin` is not in the range `[0.0, 0.5)`.
+    pub fn with_margin(&self, margin: f32) -> Self {
+        assert!((0.0..0.5).contains(&margin));
+
+        let margin_scale = 1.0 - (2.0 * margin);
+
+        Self {
+            margin,
+            margin_scale,
+            margin_scale_inv: 1.0 / margin_scale,
+            ..self.clone()
         }
     }
+
+    /// The margin in normalized units set with [`NormalMap::with_margin`].
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    /// Create a copy of this `NormalMap` with a dead zone of `width_normalized`
+    /// centered on `0.5`, where the value stays at the center of the range.
+    ///
+    /// Normalized values within `width_normalized / 2.0` of `0.5` denormalize
+    /// to the value this map returns at `0.5`, and the rest of the normalized
+    /// range is rescaled so that `0.0` and `1.0` still reach the ends of the
+    /// range. This is useful for joystick-style controls. Any existing dead
+    /// zone is replaced, and the margin is applied after the dead zone.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `width_normalized` is not in the range `(0.0, 1.0)`.
+    pub fn with_dead_zone(&self, width_normalized: f32) -> Self {
+        assert!(width_normalized > 0.0 && width_normalized < 1.0);
+
+        let dead_zone = width_normalized * 0.5;
+        let dead_zone_scale = 0.5 / (0.5 - dead_zone);
+
+        Self {
+            dead_zone,
+            dead_zone_scale,
+            dead_zone_scale_inv: 1.0 / dead_zone_scale,
+            ..self.clone()
+        }
+    }
+
+    /// The width of the dead zone in normalized units set with
+    /// [`NormalMap::with_dead_zone`].
+    pub fn dead_zone(&self) -> f32 {
+        self.dead_zone * 2.0
+    }
+
+    /// Change the range in place, keeping the same mapping and unit.
+    ///
+    /// For `Mapper::Discrete`, `min` and `max` are rounded to the nearest integer.
+    /// For `Mapper::Note`, `min` and `max` are in Hz and are rounded to the
+    /// nearest note.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the new minimum of the range
+    /// * max - the new maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    /// * Panics when either `min` or `max` <= 0.0 for `Mapper::Log2`.
+    /// * Panics when `min < center_raw < max` does not hold for `Mapper::Bilinear`.
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        match &mut self.mapper {
+            Mapper::Lin(mapper) => mapper.set_range(min, max),
+            Mapper::Pow(mapper) => mapper.set_range(min, max),
+            Mapper::Log2(mapper) => mapper.set_range(min, max),
+            Mapper::Discrete(mapper) => {
+                mapper.set_range(min.round() as isize, max.round() as isize)
+            }
+            Mapper::Bilinear(mapper) => mapper.set_range(min, max),
+            Mapper::Stepped(mapper) => mapper.set_range(min, max),
+            Mapper::Smoothstep(mapper) => mapper.set_range(min, max),
+            Mapper::Note(mapper) => {
+                mapper.set_range(mapper.nearest_note(min), mapper.nearest_note(max))
+            }
+            Mapper::Wrapping(mapper) => mapper.set_range(min, max),
+            Mapper::Piecewise(mapper) => mapper.set_range(min, max),
+        }
+    }
+
+    /// Widen the range just enough to include `value`, keeping the same
+    /// mapping and unit.
+    ///
+    /// If `value` is below the minimum it becomes the new minimum, and if it is
+    /// above the maximum it becomes the new maximum, so `normalize(value)` then
+    /// returns `0.0` or `1.0`. This does nothing if `value` is already in range.
+    ///
+    /// This is useful for hosts that auto-expand a parameter's range when the
+    /// user types a value slightly beyond it.
+    ///
+    /// For `Unit::Decibels`, `value` is a raw amplitude. Values that cannot be
+    /// included are ignored: amplitudes `<= 0.0` for `Unit::Decibels`, and
+    /// values `<= 0.0` for `Mapper::Log2`, which keeps the range positive.
+    /// See [`NormalMap::set_range`] for how the range is rounded for
+    /// `Mapper::Discrete` and `Mapper::Note`.
+    pub fn extend_to_include(&mut self, value: f32) {
+        let descriptor = self.describe();
+
+        let value = match descriptor.unit {
+            Unit::Decibels { .. } if value <= 0.0 => return,
+            Unit::Decibels { .. } => linear_base::coeff_to_db(value),
+            _ => value,
+        };
+        if descriptor.kind == CurveKind::Log2 && value <= 0.0 {
+            return;
+        }
+
+        if value < descriptor.min {
+            self.set_range(value, descriptor.max);
+        } else if value > descriptor.max {
+            self.set_range(descriptor.min, value);
+        }
+    }
+
+    /// Create a `NormalMap` whose range covers the ranges of both `self` and
+    /// `other`, from the smaller of the two minimums to the larger of the two
+    /// maximums.
+    ///
+    /// If both maps use the same kind of curve with the same exponent and unit,
+    /// the result is a copy of `self` (including its margin and any other
+    /// settings such as the step) with the combined range, as with
+    /// [`NormalMap::set_range`]. Otherwise the curves are not compatible, and the
+    /// result falls back to linear mapping, using the unit of both maps if they
+    /// share one or `Unit::Generic` if not.
+    ///
+    /// This is useful when two controls are merged into one.
+    pub fn union(&self, other: &NormalMap) -> NormalMap {
+        let a = self.describe();
+        let b = other.describe();
+
+        let min = a.min.min(b.min);
+        let max = a.max.max(b.max);
+
+        if a.kind == b.kind && a.exponent == b.exponent && a.unit == b.unit {
+            let mut map = self.clone();
+            map.set_range(min, max);
+            return map;
+        }
+
+        let unit = if a.unit == b.unit {
+            a.unit
+        } else {
+            Unit::Generic
+        };
+
+        NormalMap::linear(min, max, unit)
+    }
+
+    /// Returns `true` if the range has zero width, meaning every value maps
+    /// to the same normalized value.
+    ///
+    /// This is useful for sanity-checking parameter tables at startup.
+    pub fn is_degenerate(&self) -> bool {
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.is_degenerate(),
+            Mapper::Pow(mapper) => mapper.is_degenerate(),
+            Mapper::Log2(mapper) => mapper.is_degenerate(),
+            Mapper::Discrete(mapper) => mapper.is_degenerate(),
+            Mapper::Bilinear(mapper) => mapper.is_degenerate(),
+            Mapper::Stepped(mapper) => mapper.is_degenerate(),
+            Mapper::Smoothstep(mapper) => mapper.is_degenerate(),
+            Mapper::Note(mapper) => mapper.is_degenerate(),
+            Mapper::Wrapping(mapper) => mapper.is_degenerate(),
+            Mapper::Piecewise(mapper) => mapper.is_degenerate(),
+        }
+    }
+
+    /// Returns `true` if the current mapper uses `Unit::Decibels`.
+    ///
+    /// Only `Mapper::Lin` and `Mapper::Pow` can use decibel units.
+    pub fn is_decibels(&self) -> bool {
+        match &self.mapper {
+            Mapper::Lin(mapper) => matches!(mapper.unit(), Unit::Decibels { .. }),
+            Mapper::Pow(mapper) => matches!(mapper.unit(), Unit::Decibels { .. }),
+            _ => false,
+        }
+    }
+
+    /// Clamp an `f32` value to the range of the map without normalizing it.
+    ///
+    /// For `Mapper::Wrapping`, the value is wrapped into the range instead.
+    ///
+    /// This is useful for displaying a value that may be out of range.
+    pub fn clamp(&self, value: f32) -> f32 {
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.clamp(value),
+            Mapper::Pow(mapper) => mapper.clamp(value),
+            Mapper::Log2(mapper) => mapper.clamp(value),
+            Mapper::Discrete(mapper) => mapper.clamp(value),
+            Mapper::Bilinear(mapper) => mapper.clamp(value),
+            Mapper::Stepped(mapper) => mapper.clamp(value),
+            Mapper::Smoothstep(mapper) => mapper.clamp(value),
+            Mapper::Note(mapper) => mapper.clamp(value),
+            Mapper::Wrapping(mapper) => mapper.clamp(value),
+            Mapper::Piecewise(mapper) => mapper.clamp(value),
+        }
+    }
+
+    /// Format an `f32` value for display with the given number of decimal places.
+    ///
+    /// For `Unit::Decibels`, the raw amplitude is displayed in decibels, and
+    /// amplitudes at or below the `neg_infinity_clamp` are displayed as `-inf dB`.
+    /// For `Unit::Frequency`, values are displayed in Hz, or kHz at and above 1000 Hz.
+    /// For `Unit::Percent`, values are displayed with a `%` suffix.
+    /// Discrete values are always displayed as whole numbers.
+    pub fn format_value(&self, value: f32, decimals: usize) -> String {
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.format_value(value, decimals),
+            Mapper::Pow(mapper) => mapper.format_value(value, decimals),
+            Mapper::Log2(mapper) => mapper.format_value(value, decimals),
+            Mapper::Discrete(mapper) => mapper.format_value(value),
+            Mapper::Bilinear(mapper) => mapper.format_value(value, decimals),
+            Mapper::Stepped(mapper) => mapper.format_value(value, decimals),
+            Mapper::Smoothstep(mapper) => mapper.format_value(value, decimals),
+            Mapper::Note(mapper) => mapper.format_value(value, decimals),
+            Mapper::Wrapping(mapper) => mapper.format_value(value, decimals),
+            Mapper::Piecewise(mapper) => mapper.format_value(value, decimals),
+        }
+    }
+
+    /// Map an `f32` value to the normalized range `[0.0, 1.0]`.
+    pub fn normalize(&self, value: f32) -> f32 {
+        debug_assert!(value.is_finite());
+
+        #[cfg(feature = "tracing")]
+        if self.clamps_value(value) {
+            self.trace_clamp("normalize", value);
+        }
+
+        let normalized = match &self.mapper {
+            Mapper::Lin(mapper) => mapper.normalize(value),
+            Mapper::Pow(mapper) => mapper.normalize(value),
+            Mapper::Log2(mapper) => mapper.normalize(value),
+            Mapper::Discrete(mapper) => mapper.normalize_float(value),
+            Mapper::Bilinear(mapper) => mapper.normalize(value),
+            Mapper::Stepped(mapper) => mapper.normalize(value),
+            Mapper::Smoothstep(mapper) => mapper.normalize(value),
+            Mapper::Note(mapper) => mapper.normalize(value),
+            Mapper::Wrapping(mapper) => mapper.normalize(value),
+            Mapper::Piecewise(mapper) => mapper.normalize(value),
+        };
+
+        if self.is_remapped() {
+            self.to_outer_normalized(normalized)
+        } else {
+            normalized
+        }
+    }
+
+    /// Map an array of `f32` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[f32], out_normalized: &mut [f32]) {
+        #[cfg(feature = "tracing")]
+        self.trace_clamped_values("normalize_array", in_values, out_normalized.len());
+
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.normalize_array(in_values, out_normalized),
+            Mapper::Pow(mapper) => mapper.normalize_array(in_values, out_normalized),
+            Mapper::Log2(mapper) => mapper.normalize_array(in_values, out_normalized),
+            Mapper::Discrete(mapper) => mapper.normalize_array_float(in_values, out_normalized),
+            Mapper::Bilinear(mapper) => mapper.normalize_array(in_values, out_normalized),
+            Mapper::Stepped(mapper) => mapper.normalize_array(in_values, out_normalized),
+            Mapper::Smoothstep(mapper) => mapper.normalize_array(in_values, out_normalized),
+            Mapper::Note(mapper) => mapper.normalize_array(in_values, out_normalized),
+            Mapper::Wrapping(mapper) => mapper.normalize_array(in_values, out_normalized),
+            Mapper::Piecewise(mapper) => mapper.normalize_array(in_values, out_normalized),
+        }
+
+        if self.is_remapped() {
+            let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+
+            for normalized in out_normalized[..min_len].iter_mut() {
+                *normalized = self.to_outer_normalized(*normalized);
+            }
+        }
+    }
+
+    /// Round an `f32` value to the precision it is displayed with by
+    /// [`NormalMap::format_value`] with `decimals` decimal places, then map
+    /// it to the normalized range `[0.0, 1.0]`.
+    ///
+    /// This keeps the normalized value consistent with what the user sees, so
+    /// that a drag landing on `1.2300001` or `1.2299999` normalizes the same as
+    /// typing `1.23`. The rounding follows the display of each unit: decibels
+    /// for `Unit::Decibels`, kHz at and above 1000 Hz for `Unit::Frequency`, and
+    /// whole numbers for `Mapper::Discrete`.
+    pub fn normalize_quantized(&self, value: f32, decimals: usize) -> f32 {
+        let scale = 10.0f32.powi(decimals as i32);
+        let round = |value: f32| (value * scale).round() / scale;
+
+        let quantized = match (&self.mapper, self.describe().unit) {
+            (Mapper::Discrete(_), _) => value.round(),
+            (_, Unit::Decibels { .. }) if value <= 0.0 => value,
+            (_, Unit::Decibels { .. }) => {
+                linear_base::db_to_coeff(round(linear_base::coeff_to_db(value)))
+            }
+            (_, Unit::Frequency) if value.abs() >= 1000.0 => round(value * 0.001) * 1000.0,
+            _ => round(value),
+        };
+
+        self.normalize(quantized)
+    }
+
+    /// Map an `f32` value to the normalized range `[0.0, 1.0]`, then snap it
+    /// to the nearest of `bins` evenly spaced normalized levels
+    /// `0.0, 1.0 / (bins - 1), ..., 1.0`.
+    ///
+    /// This is useful for quantizing any curve, such as for a stepped LFO shape.
+    /// Unlike a discrete map, the levels are evenly spaced in the normalized range,
+    /// not in the raw range.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `bins == 0`.
+    pub fn quantize_normalized(&self, value: f32, bins: usize) -> f32 {
+        quantize(self.normalize(value), bins)
+    }
+
+    /// Map an array of `f32` values to the normalized range `[0.0, 1.0]`, then
+    /// snap them to the nearest of `bins` evenly spaced normalized levels
+    /// (see [`NormalMap::quantize_normalized`]).
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `bins == 0`.
+    pub fn quantize_normalized_array(
+        &self,
+        in_values: &[f32],
+        out_normalized: &mut [f32],
+        bins: usize,
+    ) {
+        assert!(bins > 0);
+
+        let min_len = self.normalize_array_n(in_values, out_normalized);
+
+        for normalized in out_normalized[..min_len].iter_mut() {
+            *normalized = quantize(*normalized, bins);
+        }
+    }
+
+    /// Map an array of discrete `isize` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// A supplied enum may be used as well as long
+    /// as it implements `From<isize> + Into<isize> + Copy + Clone`.
+    ///
+    /// This is intended for use with `Mapper::Discrete`. With any other mapper,
+    /// the values are converted to `f32` and normalized as usual.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_discrete_array<T>(&self, in_values: &[T], out_normalized: &mut [f32])
+    where
+        T: Into<isize> + Copy + Clone,
+    {
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        let input = &in_values[..min_len];
+        let output = &mut out_normalized[..min_len];
+
+        match &self.mapper {
+            Mapper::Discrete(mapper) => {
+                mapper.normalize_array(input, output);
+
+                if self.is_remapped() {
+                    for normalized in output.iter_mut() {
+                        *normalized = self.to_outer_normalized(*normalized);
+                    }
+                }
+            }
+            _ => {
+                for i in 0..min_len {
+                    let value: isize = input[i].into();
+                    output[i] = self.normalize(value as f32);
+                }
+            }
+        }
+    }
+
+    /// Map an array of `f32` values to the normalized range `[0.0, 1.0]`,
+    /// returning the number of values that were processed.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array_n(&self, in_values: &[f32], out_normalized: &mut [f32]) -> usize {
+        self.normalize_array(in_values, out_normalized);
+        std::cmp::min(in_values.len(), out_normalized.len())
+    }
+
+    /// Map an array of `f32` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Unlike [`NormalMap::normalize_array`], this returns an error instead
+    /// of silently truncating when the arrays differ in length.
+    pub fn try_normalize_array(
+        &self,
+        in_values: &[f32],
+        out_normalized: &mut [f32],
+    ) -> Result<(), LengthMismatch> {
+        check_lengths(in_values.len(), out_normalized.len())?;
+        self.normalize_array(in_values, out_normalized);
+        Ok(())
+    }
+
+    /// Map an array of `f32` values to the normalized range `[0.0, 1.0]`,
+    /// returning the number of values that were outside of the range of
+    /// the map and thus clamped.
+    ///
+    /// The normalized values are the same as with [`NormalMap::normalize_array`].
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array_counting(&self, in_values: &[f32], out_normalized: &mut [f32]) -> usize {
+        self.normalize_array(in_values, out_normalized);
+
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+
+        in_values[..min_len]
+            .iter()
+            .filter(|&&value| self.clamp(value) != value)
+            .count()
+    }
+
+    /// Map a raw amplitude to the normalized range `[0.0, 1.0]`.
+    ///
+    /// This is the same as [`NormalMap::normalize`], but states that the input
+    /// is an amplitude and not decibels.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when the unit is not `Unit::Decibels`.
+    pub fn amp_to_normalized(&self, amplitude: f32) -> f32 {
+        assert!(
+            self.is_decibels(),
+            "amp_to_normalized requires a map with `Unit::Decibels`"
+        );
+
+        self.normalize(amplitude)
+    }
+
+    /// Map a value in decibels to the normalized range `[0.0, 1.0]`, converting
+    /// it to a raw amplitude first.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when the unit is not `Unit::Decibels`.
+    pub fn db_to_normalized(&self, db: f32) -> f32 {
+        assert!(
+            self.is_decibels(),
+            "db_to_normalized requires a map with `Unit::Decibels`"
+        );
+
+        self.normalize(linear_base::db_to_coeff(db))
+    }
+
+    /// Un-map a normalized value to the corresponding `f32` value.
+    pub fn denormalize(&self, normalized: f32) -> f32 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
+
+        #[cfg(feature = "tracing")]
+        if self.clamps_normalized(normalized) {
+            self.trace_clamp("denormalize", normalized);
+        }
+
+        self.denormalize_inner(normalized)
+    }
+
+    /// Un-map a normalized value that is known to be in the range `[0.0, 1.0]`
+    /// to the corresponding `f32` value.
+    ///
+    /// Since the invariant of `Normalized` holds, this skips the debug range
+    /// check (and the trace event of the `tracing` feature) of
+    /// [`NormalMap::denormalize`]. The value is otherwise un-mapped the same
+    /// way, including the boundary checks of the underlying mapper.
+    pub fn denormalize_checked(&self, normalized: Normalized) -> f32 {
+        self.denormalize_inner(normalized.get())
+    }
+
+    /// Un-map a normalized value to the corresponding `f32` value without the
+    /// checks of [`NormalMap::denormalize`], applying the margin and dead zone
+    /// only when either is set.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn denormalize_inner(&self, normalized: f32) -> f32 {
+        if self.is_remapped() {
+            self.denormalize_mapper(self.to_mapper_normalized(normalized))
+        } else {
+            self.denormalize_mapper(normalized)
+        }
+    }
+
+    /// Un-map a normalized value of the underlying mapper to the
+    /// corresponding `f32` value.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn denormalize_mapper(&self, normalized: f32) -> f32 {
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.denormalize(normalized),
+            Mapper::Pow(mapper) => mapper.denormalize(normalized),
+            Mapper::Log2(mapper) => mapper.denormalize(normalized),
+            Mapper::Discrete(mapper) => mapper.denormalize_float(normalized),
+            Mapper::Bilinear(mapper) => mapper.denormalize(normalized),
+            Mapper::Stepped(mapper) => mapper.denormalize(normalized),
+            Mapper::Smoothstep(mapper) => mapper.denormalize(normalized),
+            Mapper::Note(mapper) => mapper.denormalize(normalized),
+            Mapper::Wrapping(mapper) => mapper.denormalize(normalized),
+            Mapper::Piecewise(mapper) => mapper.denormalize(normalized),
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding `f32` value, then
+    /// multiply it by the amplitude of a trim gain in decibels.
+    ///
+    /// This is useful in a mixer strip where a fader and a trim control both
+    /// affect the same signal. For `Unit::Decibels` the denormalized value is
+    /// already a raw amplitude, so the result is also a raw amplitude.
+    pub fn denormalize_with_trim_db(&self, normalized: f32, trim_db: f32) -> f32 {
+        self.denormalize(normalized) * linear_base::db_to_coeff(trim_db)
+    }
+
+    /// Map an `f32` value to the normalized range `[0.0, 1.0]` using the curve
+    /// of this map, but over the range `[min, max]` for this call only.
+    ///
+    /// This gives the same result as calling [`NormalMap::set_range`] on a copy
+    /// of this map, so the shape of the curve carries over unchanged: the
+    /// exponent of `Mapper::Pow` is applied within the temporary bounds, as
+    /// are the step of `Mapper::Stepped` and the margin. For `Unit::Decibels`,
+    /// `min` and `max` are in decibels.
+    ///
+    /// This is useful for a temporary zoom in a curve editor. The map is not
+    /// modified, and nothing is allocated except for `Mapper::Piecewise` and a
+    /// grouped `Mapper::Discrete`.
+    ///
+    /// # Panics
+    ///
+    /// * Panics in the same cases as [`NormalMap::set_range`].
+    pub fn normalize_in_range(&self, value: f32, min: f32, max: f32) -> f32 {
+        let mut map = self.clone();
+        map.set_range(min, max);
+
+        map.normalize(value)
+    }
+
+    /// Map an `f32` value to the normalized range `[0.0, 1.0]`, then rescale it
+    /// so that the full range of this map only spans `[window.0, window.1]`.
+    ///
+    /// This is useful for a macro control that only drives a sub-section of a
+    /// parameter's normalized range.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `0.0 <= window.0 < window.1 <= 1.0` does not hold.
+    pub fn normalize_windowed(&self, value: f32, window: (f32, f32)) -> f32 {
+        assert_window(window);
+
+        window.0 + (self.normalize(value) * (window.1 - window.0))
+    }
+
+    /// Un-map a normalized value in `[window.0, window.1]` to the corresponding
+    /// `f32` value. This is the inverse of [`NormalMap::normalize_windowed`].
+    ///
+    /// Normalized values outside of the window are clamped to it.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `0.0 <= window.0 < window.1 <= 1.0` does not hold.
+    pub fn denormalize_windowed(&self, normalized: f32, window: (f32, f32)) -> f32 {
+        assert_window(window);
+
+        let normalized = (normalized - window.0) / (window.1 - window.0);
+
+        self.denormalize(normalized.clamp(0.0, 1.0))
+    }
+
+    /// Map an `f32` value to a 14-bit integer in the range `[0, 16383]`, such
+    /// as for a MIDI high-resolution control change (MSB + LSB).
+    ///
+    /// The normalized value is rounded to the nearest integer, and saturates
+    /// at either end of the range.
+    pub fn normalize_to_u14(&self, value: f32) -> u16 {
+        (self.normalize(value).clamp(0.0, 1.0) * U14_MAX).round() as u16
+    }
+
+    /// Un-map a 14-bit integer in the range `[0, 16383]` to the corresponding
+    /// `f32` value. This is the inverse of [`NormalMap::normalize_to_u14`].
+    ///
+    /// Integers above `16383` saturate to the maximum of the range.
+    pub fn denormalize_from_u14(&self, value: u16) -> f32 {
+        self.denormalize((f32::from(value) / U14_MAX).min(1.0))
+    }
+
+    /// Add a relative `delta` to a normalized value, clamping the result to
+    /// the normalized range `[0.0, 1.0]`.
+    ///
+    /// This is useful for applying the relative movement of an encoder or a
+    /// scroll wheel, such as turning a knob by `0.05` per wheel step.
+    pub fn nudge(&self, current_normalized: f32, delta: f32) -> f32 {
+        (current_normalized + delta).clamp(0.0, 1.0)
+    }
+
+    /// Add a relative `delta_normalized` to the normalized position of an
+    /// `f32` value, returning the new `f32` value.
+    ///
+    /// The adjustment is applied in normalized space, so a given delta moves
+    /// the control by the same visual amount regardless of the curve.
+    pub fn nudge_value(&self, current_raw: f32, delta_normalized: f32) -> f32 {
+        self.denormalize(self.nudge(self.normalize(current_raw), delta_normalized))
+    }
+
+    /// Snap a normalized value to the normalized position of `target_raw`
+    /// when it is within `radius_normalized` of it, otherwise return it
+    /// unchanged.
+    ///
+    /// This gives a control a detent around a "home" value on any curve, such
+    /// as 0 dB on a gain fader or the center of a pan knob.
+    pub fn snap_to(&self, normalized: f32, target_raw: f32, radius_normalized: f32) -> f32 {
+        let target = self.normalize(target_raw);
+
+        if (normalized - target).abs() <= radius_normalized {
+            target
+        } else {
+            normalized
+        }
+    }
+
+    /// Mirror a normalized value across the center of the normalized range,
+    /// so `0.2` becomes `0.8` and `0.9` becomes `0.1`.
+    pub fn reflect(&self, normalized: f32) -> f32 {
+        1.0 - normalized
+    }
+
+    /// Mirror an `f32` value across the center of the normalized range,
+    /// returning the new `f32` value.
+    ///
+    /// The value is reflected on the curve of the map, not linearly in raw
+    /// space. This is useful for an "invert" button on a control.
+    pub fn reflect_value(&self, raw: f32) -> f32 {
+        self.denormalize(self.reflect(self.normalize(raw)))
+    }
+
+    /// Approximately how much the normalized value must change to move the
+    /// `f32` value by `raw_delta` near the normalized position `at_normalized`.
+    ///
+    /// This is `raw_delta / range` for a linear map, and uses the local slope
+    /// of the curve otherwise. This is useful for stepping a nonlinear control
+    /// by a fixed raw amount, such as one semitone per key press.
+    ///
+    /// This returns `0.0` where the curve is flat, such as within a step of a
+    /// discrete map or when the range has zero width.
+    pub fn normalized_delta_for(&self, at_normalized: f32, raw_delta: f32) -> f32 {
+        let slope = self.slope_at(at_normalized);
+        if slope == 0.0 || !slope.is_finite() {
+            return 0.0;
+        }
+
+        raw_delta / slope
+    }
+
+    /// How many raw units one normalized unit covers near the normalized
+    /// position `normalized`, which is the magnitude of the local slope of
+    /// the curve.
+    ///
+    /// Smaller values mean finer resolution at that position. This is useful
+    /// for comparing two candidate curves for the same range, such as
+    /// `power(.., 2.0, ..)` and `log2`, at the positions that matter most.
+    pub fn resolution_at(&self, normalized: f32) -> f32 {
+        self.slope_at(normalized).abs()
+    }
+
+    /// Return the normalized intervals `(start, end)` where quantization of the
+    /// raw value is coarse, meaning the local resolution (see
+    /// [`NormalMap::resolution_at`]) times `f32::EPSILON` exceeds `threshold`.
+    ///
+    /// The range is checked in segments of one normalized percent, and
+    /// adjacent flagged segments are merged into a single interval. An empty
+    /// list means the precision is sufficient everywhere.
+    ///
+    /// This is useful for automatically catching parameter definitions whose
+    /// range is too large for the precision in use.
+    pub fn low_resolution_regions(&self, threshold: f32) -> Vec<(f32, f32)> {
+        let mut regions: Vec<(f32, f32)> = Vec::new();
+
+        for i in 0..RESOLUTION_SEGMENTS {
+            let start = i as f32 / RESOLUTION_SEGMENTS as f32;
+            let end = (i + 1) as f32 / RESOLUTION_SEGMENTS as f32;
+            let midpoint = (start + end) * 0.5;
+
+            if self.resolution_at(midpoint) * f32::EPSILON <= threshold {
+                continue;
+            }
+
+            match regions.last_mut() {
+                Some(region) if region.1 == start => region.1 = end,
+                _ => regions.push((start, end)),
+            }
+        }
+
+        regions
+    }
+
+    /// The local slope of the curve at a normalized position, estimated with
+    /// a central difference.
+    fn slope_at(&self, normalized: f32) -> f32 {
+        let h = f32::EPSILON.sqrt();
+        let a = (normalized - h).max(0.0);
+        let b = (normalized + h).min(1.0);
+
+        (self.denormalize(b) - self.denormalize(a)) / (b - a)
+    }
+
+    /// Un-map a normalized value to the corresponding offset in cents from
+    /// the geometric center of the range.
+    ///
+    /// This returns `None` if the current mapper is not `Mapper::Log2`.
+    pub fn denormalize_to_cents(&self, normalized: f32) -> Option<f32> {
+        match &self.mapper {
+            Mapper::Log2(mapper) => {
+                Some(mapper.denormalize_to_cents(self.to_mapper_normalized(normalized)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f32` value.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[f32], out_values: &mut [f32]) {
+        #[cfg(feature = "tracing")]
+        self.trace_clamped_normalized("denormalize_array", in_normalized, out_values.len());
+
+        if self.is_remapped() {
+            let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+            let input = &in_normalized[..min_len];
+            let output = &mut out_values[..min_len];
+
+            // Like the mapper array methods, this doesn't check the range of
+            // each normalized value.
+            for i in 0..min_len {
+                output[i] = self.denormalize_inner(input[i]);
+            }
+
+            return;
+        }
+
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.denormalize_array(in_normalized, out_values),
+            Mapper::Pow(mapper) => mapper.denormalize_array(in_normalized, out_values),
+            Mapper::Log2(mapper) => mapper.denormalize_array(in_normalized, out_values),
+            Mapper::Discrete(mapper) => mapper.denormalize_array_float(in_normalized, out_values),
+            Mapper::Bilinear(mapper) => mapper.denormalize_array(in_normalized, out_values),
+            Mapper::Stepped(mapper) => mapper.denormalize_array(in_normalized, out_values),
+            Mapper::Smoothstep(mapper) => mapper.denormalize_array(in_normalized, out_values),
+            Mapper::Note(mapper) => mapper.denormalize_array(in_normalized, out_values),
+            Mapper::Wrapping(mapper) => mapper.denormalize_array(in_normalized, out_values),
+            Mapper::Piecewise(mapper) => mapper.denormalize_array(in_normalized, out_values),
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding `f32` value, flushing
+    /// a subnormal result to `0.0`.
+    ///
+    /// Subnormal values can cause CPU spikes in realtime audio processing. These
+    /// are mostly produced by very low amplitudes with `Unit::Decibels`.
+    pub fn denormalize_ftz(&self, normalized: f32) -> f32 {
+        flush_subnormal(self.denormalize(normalized))
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f32` value,
+    /// flushing subnormal results to `0.0`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array_ftz(&self, in_normalized: &[f32], out_values: &mut [f32]) {
+        self.denormalize_array(in_normalized, out_values);
+
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+
+        for value in out_values[..min_len].iter_mut() {
+            *value = flush_subnormal(*value);
+        }
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f32` value,
+    /// returning the number of values that were processed.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array_n(&self, in_normalized: &[f32], out_values: &mut [f32]) -> usize {
+        self.denormalize_array(in_normalized, out_values);
+        std::cmp::min(in_normalized.len(), out_values.len())
+    }
+
+    /// Map an array of `f32` values to the normalized range `[0.0, 1.0]`,
+    /// distributing the work across the rayon thread pool.
+    ///
+    /// This is intended for offline processing of very large arrays, such as
+    /// rendering an entire automation curve for export. Do not use this on the
+    /// audio thread. Arrays shorter than a few thousand values are processed
+    /// serially.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    #[cfg(feature = "rayon")]
+    pub fn normalize_array_par(&self, in_values: &[f32], out_normalized: &mut [f32]) {
+        use rayon::prelude::*;
+
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        if min_len <= PAR_CHUNK_LEN {
+            self.normalize_array(in_values, out_normalized);
+            return;
+        }
+
+        in_values[..min_len]
+            .par_chunks(PAR_CHUNK_LEN)
+            .zip(out_normalized[..min_len].par_chunks_mut(PAR_CHUNK_LEN))
+            .for_each(|(input, output)| self.normalize_array(input, output));
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f32` value,
+    /// distributing the work across the rayon thread pool.
+    ///
+    /// This is intended for offline processing of very large arrays, such as
+    /// rendering an entire automation curve for export. Do not use this on the
+    /// audio thread. Arrays shorter than a few thousand values are processed
+    /// serially.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    #[cfg(feature = "rayon")]
+    pub fn denormalize_array_par(&self, in_normalized: &[f32], out_values: &mut [f32]) {
+        use rayon::prelude::*;
+
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        if min_len <= PAR_CHUNK_LEN {
+            self.denormalize_array(in_normalized, out_values);
+            return;
+        }
+
+        in_normalized[..min_len]
+            .par_chunks(PAR_CHUNK_LEN)
+            .zip(out_values[..min_len].par_chunks_mut(PAR_CHUNK_LEN))
+            .for_each(|(input, output)| self.denormalize_array(input, output));
+    }
+
+    /// Un-map a single normalized value and fill the entire output array
+    /// with the result.
+    ///
+    /// This is useful when a parameter is held constant over a block.
+    pub fn denormalize_fill(&self, normalized: f32, out_values: &mut [f32]) {
+        out_values.fill(self.denormalize(normalized));
+    }
+
+    /// Interpolate between two `f32` values along the curve of the map, where
+    /// a `t` of `0.0` returns `from_raw` and a `t` of `1.0` returns `to_raw`.
+    ///
+    /// Both values are normalized, linearly interpolated by `t` in normalized
+    /// space, and denormalized. For a logarithmic frequency map this gives a
+    /// musically even glide, unlike interpolating the raw values.
+    ///
+    /// A `t` outside of `[0.0, 1.0]` extrapolates, and the result is clamped to
+    /// the range of the map.
+    pub fn interpolate_values(&self, from_raw: f32, to_raw: f32, t: f32) -> f32 {
+        let from = self.normalize(from_raw);
+        let to = self.normalize(to_raw);
+
+        self.denormalize_inner((from + ((to - from) * t)).clamp(0.0, 1.0))
+    }
+
+    /// Interpolate between two `f32` values along the curve of the map for
+    /// each `t` in an array, such as when rendering a whole sweep.
+    ///
+    /// See [`NormalMap::interpolate_values`] for details.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn interpolate_values_array(
+        &self,
+        from_raw: f32,
+        to_raw: f32,
+        in_t: &[f32],
+        out_values: &mut [f32],
+    ) {
+        let from = self.normalize(from_raw);
+        let to = self.normalize(to_raw);
+
+        let min_len = std::cmp::min(in_t.len(), out_values.len());
+        let input = &in_t[..min_len];
+        let output = &mut out_values[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.denormalize_inner((from + ((to - from) * input[i])).clamp(0.0, 1.0));
+        }
+    }
+
+    /// Un-map a normalized value through both `a` and `b`, then linearly
+    /// blend the two results, where a `blend` of `0.0` returns the value of
+    /// `a` and a `blend` of `1.0` returns the value of `b`.
+    ///
+    /// This is useful for morphing between two curves. Both maps should cover
+    /// compatible ranges. If they don't, the result still lies between the
+    /// two denormalized values, but it will not follow either range.
+    pub fn lerp_denormalize(a: &NormalMap, b: &NormalMap, normalized: f32, blend: f32) -> f32 {
+        let a_value = a.denormalize(normalized);
+
+        a_value + ((b.denormalize(normalized) - a_value) * blend)
+    }
+
+    /// Un-map an array of normalized values through both `a` and `b`, then
+    /// linearly blend the two results (see [`NormalMap::lerp_denormalize`]).
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn lerp_denormalize_array(
+        a: &NormalMap,
+        b: &NormalMap,
+        in_normalized: &[f32],
+        out_values: &mut [f32],
+        blend: f32,
+    ) {
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        let input = &in_normalized[..min_len];
+        let output = &mut out_values[..min_len];
+
+        a.denormalize_array(input, output);
+
+        for i in 0..min_len {
+            output[i] += (b.denormalize(input[i]) - output[i]) * blend;
+        }
+    }
+
+    /// Return the denormalized values at `count` evenly spaced normalized
+    /// positions `0.0, 1.0 / (count - 1), ..., 1.0`.
+    ///
+    /// This is useful for drawing tick marks on a parameter scale.
+    pub fn ticks(&self, count: usize) -> Vec<f32> {
+        (0..count)
+            .map(|i| self.denormalize(sample_position(i, count)))
+            .collect()
+    }
+
+    /// Lazily yield `(normalized, value)` pairs at `n` evenly spaced normalized
+    /// positions `0.0, 1.0 / (n - 1), ..., 1.0`.
+    ///
+    /// Both endpoints are included, so the first pair is at exactly `0.0` and
+    /// the last at exactly `1.0` (when `n >= 2`). This is the lazy counterpart
+    /// to [`NormalMap::ticks`], useful for exporting large automation tables
+    /// without allocating.
+    pub fn iter_samples(&self, n: usize) -> impl Iterator<Item = (f32, f32)> + '_ {
+        (0..n).map(move |i| {
+            let normalized = sample_position(i, n);
+            (normalized, self.denormalize(normalized))
+        })
+    }
+
+    /// Return the denormalized values at `n` evenly spaced normalized positions
+    /// `0.0, 1.0 / (n - 1), ..., 1.0`, as a flat list of y-values.
+    ///
+    /// This returns the same values as [`NormalMap::ticks`], in the shape a
+    /// plotting library expects when the x-axis is already known.
+    pub fn transfer_curve(&self, n: usize) -> Vec<f32> {
+        self.ticks(n)
+    }
+
+    /// Render the curve as an ASCII chart of `height` lines of `width`
+    /// characters, each ending with a newline.
+    ///
+    /// Each column is a normalized position from `0.0` on the left to `1.0` on
+    /// the right, and the `*` in it marks the denormalized value, scaled so that
+    /// the smallest value is on the bottom line and the largest is on the top.
+    ///
+    /// This is useful for quickly checking the shape of a curve in a terminal.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `width` or `height` is 0.
+    pub fn ascii_plot(&self, width: usize, height: usize) -> String {
+        assert!(width > 0 && height > 0);
+
+        let values = self.ticks(width);
+
+        let lowest = values.iter().copied().fold(f32::INFINITY, f32::min);
+        let highest = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let span = highest - lowest;
+
+        let mut grid = vec![vec![' '; width]; height];
+        for (column, value) in values.iter().enumerate() {
+            let level = if span > 0.0 {
+                ((value - lowest) / span * (height - 1) as f32).round() as usize
+            } else {
+                0
+            };
+
+            grid[height - 1 - level.min(height - 1)][column] = '*';
+        }
+
+        let mut plot = String::with_capacity((width + 1) * height);
+        for line in grid.iter() {
+            plot.extend(line.iter());
+            plot.push('\n');
+        }
+
+        plot
+    }
+
+    /// Return how many distinct `f32` values the map produces when denormalizing
+    /// `normalized_steps` evenly spaced normalized positions.
+    ///
+    /// If this is less than `normalized_steps`, then some positions collapse to
+    /// the same value due to the precision of `f32`. This is useful for deciding
+    /// whether a parameter needs more precision.
+    pub fn effective_resolution(&self, normalized_steps: usize) -> usize {
+        let mut values: Vec<f32> = (0..normalized_steps)
+            .map(|i| self.denormalize(sample_position(i, normalized_steps)))
+            .collect();
+
+        values.sort_unstable_by(|a, b| a.total_cmp(b));
+        values.dedup();
+
+        values.len()
+    }
+
+    /// Split the range of `f32` values into `bins` bins of equal width, and
+    /// return the share of the normalized range `[0.0, 1.0]` that maps into
+    /// each bin, in ascending order of value.
+    ///
+    /// The shares sum to `1.0`. For a logarithmic map the bins of low values
+    /// get a larger share, which quantifies how the curve distributes its
+    /// resolution. If the range has zero width, the first bin gets the whole
+    /// share.
+    pub fn density_histogram(&self, bins: usize) -> Vec<f32> {
+        if bins == 0 {
+            return Vec::new();
+        }
+
+        if let Mapper::Wrapping(_) = &self.mapper {
+            // Wrapping maps are linear, but their maximum normalizes to `0.0`.
+            return vec![1.0 / bins as f32; bins];
+        }
+
+        let start = self.denormalize(0.0);
+        let end = self.denormalize(1.0);
+        let (low, high) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+
+        let mut histogram = vec![0.0; bins];
+
+        if low == high {
+            histogram[0] = 1.0;
+            return histogram;
+        }
+
+        let mut last_normalized = self.normalize(low);
+
+        for (i, share) in histogram.iter_mut().enumerate() {
+            let edge = if i + 1 == bins {
+                high
+            } else {
+                low + ((high - low) * (i + 1) as f32 / bins as f32)
+            };
+            let normalized = self.normalize(edge);
+
+            *share = (normalized - last_normalized).abs();
+            last_normalized = normalized;
+        }
+
+        histogram
+    }
+
+    /// Return the maximum of `|self.denormalize(n) - other.denormalize(n)|`
+    /// over `samples` evenly spaced normalized positions `n`.
+    ///
+    /// This is useful when migrating presets across a change in a parameter's
+    /// curve, to decide whether copying the stored normalized value is
+    /// acceptable or whether the value needs to be remapped.
+    pub fn max_value_error_vs(&self, other: &NormalMap, samples: usize) -> f32 {
+        (0..samples)
+            .map(|i| {
+                let normalized = sample_position(i, samples);
+                (self.denormalize(normalized) - other.denormalize(normalized)).abs()
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// Returns `true` if `other.normalize(self.denormalize(n))` is within
+    /// `epsilon` of `n` for `samples` evenly spaced normalized positions `n`.
+    ///
+    /// This is useful for checking that a forward map and a separately
+    /// configured reverse map agree on their range and curve. Please note
+    /// that discrete and stepped maps only round trip at their steps.
+    pub fn is_inverse_of(&self, other: &NormalMap, samples: usize, epsilon: f32) -> bool {
+        (0..samples).all(|i| {
+            let normalized = sample_position(i, samples);
+            (other.normalize(self.denormalize(normalized)) - normalized).abs() <= epsilon
+        })
+    }
+
+    /// Return `(normalized, value)` points along the curve of this map, in
+    /// ascending order of `normalized`, for drawing it as a polyline.
+    ///
+    /// Starting from a few evenly spaced points, the segment whose midpoint
+    /// deviates the most from its straight chord is repeatedly split in half,
+    /// until no midpoint deviates by more than `max_error` (in the units of the
+    /// denormalized values) or there are `max_points` points. This places few
+    /// points on nearly straight stretches and more where the curve bends.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `max_points < 2`.
+    pub fn adaptive_samples(&self, max_error: f32, max_points: usize) -> Vec<(f32, f32)> {
+        assert!(max_points >= 2);
+
+        let chord_error = |a: (f32, f32), b: (f32, f32)| {
+            let mid = self.denormalize(0.5 * (a.0 + b.0));
+            (mid - (0.5 * (a.1 + b.1))).abs()
+        };
+
+        let initial_points = std::cmp::min(max_points, 5);
+        let mut points: Vec<(f32, f32)> = (0..initial_points)
+            .map(|i| {
+                let normalized = sample_position(i, initial_points);
+                (normalized, self.denormalize(normalized))
+            })
+            .collect();
+        let mut errors: Vec<f32> = points
+            .windows(2)
+            .map(|segment| chord_error(segment[0], segment[1]))
+            .collect();
+
+        while points.len() < max_points {
+            let (i, &error) = errors
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .unwrap();
+            if error <= max_error {
+                break;
+            }
+
+            let normalized = 0.5 * (points[i].0 + points[i + 1].0);
+            let mid = (normalized, self.denormalize(normalized));
+
+            points.insert(i + 1, mid);
+            errors[i] = chord_error(points[i], mid);
+            errors.insert(i + 1, chord_error(mid, points[i + 2]));
+        }
+
+        points
+    }
+
+    /// Return the normalized positions of round values (see [`TickSpacing`])
+    /// that fall within the range of the map, in ascending order of value.
+    ///
+    /// This is useful for labeling an axis. For `Mapper::Log2`, the round values
+    /// are taken from every decade in the range, such as `20, 50, 100, 200, ...`.
+    /// For all other mappers, they are the multiples of a single round step,
+    /// chosen so that there are at most about ten intervals.
+    ///
+    /// For `Unit::Decibels`, the round values are raw amplitudes.
+    pub fn nice_tick_normals(&self, spacing: TickSpacing) -> Vec<f32> {
+        let start = self.denormalize(0.0);
+        let end = self.denormalize(1.0);
+        let (lo, hi) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+
+        if lo >= hi {
+            return Vec::new();
+        }
+
+        let multipliers: &[f32] = match spacing {
+            TickSpacing::Decade => &[1.0],
+            TickSpacing::OneTwoFive => &[1.0, 2.0, 5.0],
+        };
+
+        let mut values = Vec::new();
+
+        if let Mapper::Log2(_) = &self.mapper {
+            let first_decade = lo.log10().floor() as i32;
+            let last_decade = hi.log10().ceil() as i32;
+
+            for decade in first_decade..=last_decade {
+                let scale = 10.0f32.powi(decade);
+
+                for multiplier in multipliers.iter() {
+                    let value = multiplier * scale;
+                    if value >= lo && value <= hi {
+                        values.push(value);
+                    }
+                }
+            }
+        } else {
+            let min_step = (hi - lo) / 10.0;
+            let scale = 10.0f32.powi(min_step.log10().floor() as i32);
+            let step = multipliers
+                .iter()
+                .chain(std::iter::once(&10.0))
+                .map(|multiplier| multiplier * scale)
+                .find(|&step| step >= min_step)
+                .unwrap_or(10.0 * scale);
+
+            let first = (lo / step).ceil() as i64;
+            let last = (hi / step).floor() as i64;
+
+            values.extend((first..=last).map(|i| i as f32 * step));
+        }
+
+        values
+            .into_iter()
+            .map(|value| self.normalize(value))
+            .collect()
+    }
+
+    /// Build a lookup table of the denormalized values at `size` evenly spaced
+    /// normalized positions, for use with [`NormalMap::lut_denormalize`].
+    ///
+    /// Looking up a value is much cheaper than calling `powf` or `exp2`, which
+    /// is useful for voice-rate modulation. The accuracy depends on how curved
+    /// the map is between two entries. The error of linear interpolation shrinks
+    /// with the square of the table size, so doubling `size` roughly quarters the
+    /// error. Linear maps are reproduced exactly with any `size` of at least 2.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `size < 2`.
+    pub fn build_denormalize_lut(&self, size: usize) -> Vec<f32> {
+        assert!(size >= 2);
+
+        self.ticks(size)
+    }
+
+    /// Un-map a normalized value using a lookup table built with
+    /// [`NormalMap::build_denormalize_lut`], linearly interpolating between
+    /// the two nearest entries.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `lut` is empty.
+    pub fn lut_denormalize(lut: &[f32], normalized: f32) -> f32 {
+        assert!(!lut.is_empty());
+
+        if lut.len() == 1 {
+            return lut[0];
+        }
+
+        let last = lut.len() - 1;
+        let position = normalized.clamp(0.0, 1.0) * last as f32;
+        let i = std::cmp::min(position as usize, last - 1);
+        let fract = position - i as f32;
+
+        lut[i] + ((lut[i + 1] - lut[i]) * fract)
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f32` value,
+    /// then apply `value * gain + offset` to each result in the same pass.
+    ///
+    /// The values before `gain` and `offset` are applied are the same as with
+    /// [`NormalMap::denormalize_array`].
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array_affine(
+        &self,
+        in_normalized: &[f32],
+        out_values: &mut [f32],
+        gain: f32,
+        offset: f32,
+    ) {
+        #[cfg(feature = "tracing")]
+        self.trace_clamped_normalized("denormalize_array_affine", in_normalized, out_values.len());
+
+        let affine = |value: f32| (value * gain) + offset;
+
+        if self.is_remapped() {
+            let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+            let input = &in_normalized[..min_len];
+            let output = &mut out_values[..min_len];
+
+            for i in 0..min_len {
+                output[i] = affine(self.denormalize_mapper(self.to_mapper_normalized(input[i])));
+            }
+
+            return;
+        }
+
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.denormalize_array_with(in_normalized, out_values, affine),
+            Mapper::Pow(mapper) => mapper.denormalize_array_with(in_normalized, out_values, affine),
+            Mapper::Log2(mapper) => {
+                mapper.denormalize_array_with(in_normalized, out_values, affine)
+            }
+            Mapper::Discrete(mapper) => {
+                mapper.denormalize_array_float_with(in_normalized, out_values, affine)
+            }
+            Mapper::Bilinear(mapper) => {
+                mapper.denormalize_array_with(in_normalized, out_values, affine)
+            }
+            Mapper::Stepped(mapper) => {
+                mapper.denormalize_array_with(in_normalized, out_values, affine)
+            }
+            Mapper::Smoothstep(mapper) => {
+                mapper.denormalize_array_with(in_normalized, out_values, affine)
+            }
+            Mapper::Note(mapper) => {
+                mapper.denormalize_array_with(in_normalized, out_values, affine)
+            }
+            Mapper::Wrapping(mapper) => {
+                mapper.denormalize_array_with(in_normalized, out_values, affine)
+            }
+            Mapper::Piecewise(mapper) => {
+                mapper.denormalize_array_with(in_normalized, out_values, affine)
+            }
+        }
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f32` value.
+    ///
+    /// Unlike [`NormalMap::denormalize_array`], this returns an error instead
+    /// of silently truncating when the arrays differ in length.
+    pub fn try_denormalize_array(
+        &self,
+        in_normalized: &[f32],
+        out_values: &mut [f32],
+    ) -> Result<(), LengthMismatch> {
+        check_lengths(in_normalized.len(), out_values.len())?;
+        self.denormalize_array(in_normalized, out_values);
+        Ok(())
+    }
+
+    /// Return a closure that maps an `f32` value to the normalized range `[0.0, 1.0]`.
+    ///
+    /// This is useful for passing the mapping to utilities that expect a
+    /// plain `Fn(f32) -> f32`.
+    pub fn normalizer(&self) -> impl Fn(f32) -> f32 + '_ {
+        move |value| self.normalize(value)
+    }
+
+    /// Return a closure that un-maps a normalized value to the corresponding `f32` value.
+    ///
+    /// This is useful for passing the mapping to utilities that expect a
+    /// plain `Fn(f32) -> f32`.
+    pub fn denormalizer(&self) -> impl Fn(f32) -> f32 + '_ {
+        move |normalized| self.denormalize(normalized)
+    }
+
+    /// Returns `true` if a margin or a dead zone is set, so that normalized
+    /// values need to be remapped for the underlying mapper.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn is_remapped(&self) -> bool {
+        self.margin != 0.0 || self.dead_zone != 0.0
+    }
+
+    /// Map a normalized value of this map to the normalized value of the
+    /// underlying mapper, applying the dead zone and then the margin.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn to_mapper_normalized(&self, normalized: f32) -> f32 {
+        let normalized = normalized.clamp(0.0, 1.0);
+
+        let normalized = if normalized < 0.5 - self.dead_zone {
+            normalized * self.dead_zone_scale
+        } else if normalized > 0.5 + self.dead_zone {
+            0.5 + ((normalized - 0.5 - self.dead_zone) * self.dead_zone_scale)
+        } else {
+            0.5
+        };
+
+        self.margin + (normalized.clamp(0.0, 1.0) * self.margin_scale)
+    }
+
+    /// Map a normalized value of the underlying mapper to the normalized
+    /// value of this map, removing the margin and then the dead zone.
+    ///
+    /// A value at the center maps to `0.5`, the center of the dead zone.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn to_outer_normalized(&self, normalized: f32) -> f32 {
+        let normalized = ((normalized - self.margin) * self.margin_scale_inv).clamp(0.0, 1.0);
+
+        if self.dead_zone == 0.0 {
+            normalized
+        } else if normalized < 0.5 {
+            normalized * self.dead_zone_scale_inv
+        } else if normalized > 0.5 {
+            0.5 + self.dead_zone + ((normalized - 0.5) * self.dead_zone_scale_inv)
+        } else {
+            0.5
+        }
+    }
+
+    /// Whether `normalize` clamps `value` to the range of the map.
+    ///
+    /// A `WrappingMap` wraps out-of-range values around instead, so it never
+    /// clamps.
+    #[cfg(feature = "tracing")]
+    fn clamps_value(&self, value: f32) -> bool {
+        !matches!(self.mapper, Mapper::Wrapping(_)) && self.clamp(value) != value
+    }
+
+    /// Whether `denormalize` clamps `normalized` to the range `[0.0, 1.0]`.
+    #[cfg(feature = "tracing")]
+    fn clamps_normalized(&self, normalized: f32) -> bool {
+        !matches!(self.mapper, Mapper::Wrapping(_)) && !(0.0..=1.0).contains(&normalized)
+    }
+
+    /// Emit a trace event for each value of an array passed to `method` that
+    /// is clamped, up to the length of the shorter array.
+    #[cfg(feature = "tracing")]
+    fn trace_clamped_values(&self, method: &'static str, in_values: &[f32], out_len: usize) {
+        let min_len = std::cmp::min(in_values.len(), out_len);
+
+        for &value in in_values[..min_len].iter() {
+            if self.clamps_value(value) {
+                self.trace_clamp(method, value);
+            }
+        }
+    }
+
+    /// Emit a trace event for each normalized value of an array passed to
+    /// `method` that is clamped, up to the length of the shorter array.
+    #[cfg(feature = "tracing")]
+    fn trace_clamped_normalized(
+        &self,
+        method: &'static str,
+        in_normalized: &[f32],
+        out_len: usize,
+    ) {
+        let min_len = std::cmp::min(in_normalized.len(), out_len);
+
+        for &normalized in in_normalized[..min_len].iter() {
+            if self.clamps_normalized(normalized) {
+                self.trace_clamp(method, normalized);
+            }
+        }
+    }
+
+    /// Emit a trace event for an out-of-range value passed to `method`.
+    ///
+    /// This is kept out of line so the check in the hot path stays small.
+    #[cfg(feature = "tracing")]
+    #[cold]
+    #[inline(never)]
+    fn trace_clamp(&self, method: &'static str, value: f32) {
+        let descriptor = self.describe();
+
+        tracing::trace!(
+            method,
+            value,
+            min = descriptor.min,
+            max = descriptor.max,
+            "clamped an out-of-range value"
+        );
+    }
+}
+
+impl From<LinearMap> for Mapper {
+    fn from(mapper: LinearMap) -> Self {
+        Mapper::Lin(mapper)
+    }
+}
+
+impl From<PowerMap> for Mapper {
+    fn from(mapper: PowerMap) -> Self {
+        Mapper::Pow(mapper)
+    }
+}
+
+impl From<Log2Map> for Mapper {
+    fn from(mapper: Log2Map) -> Self {
+        Mapper::Log2(mapper)
+    }
+}
+
+impl From<DiscreteMap> for Mapper {
+    fn from(mapper: DiscreteMap) -> Self {
+        Mapper::Discrete(mapper)
+    }
+}
+
+impl From<BilinearMap> for Mapper {
+    fn from(mapper: BilinearMap) -> Self {
+        Mapper::Bilinear(mapper)
+    }
+}
+
+impl From<SteppedMap> for Mapper {
+    fn from(mapper: SteppedMap) -> Self {
+        Mapper::Stepped(mapper)
+    }
+}
+
+impl From<SmoothstepMap> for Mapper {
+    fn from(mapper: SmoothstepMap) -> Self {
+        Mapper::Smoothstep(mapper)
+    }
+}
+
+impl From<NoteMap> for Mapper {
+    fn from(mapper: NoteMap) -> Self {
+        Mapper::Note(mapper)
+    }
+}
+
+impl From<WrappingMap> for Mapper {
+    fn from(mapper: WrappingMap) -> Self {
+        Mapper::Wrapping(mapper)
+    }
+}
+
+impl From<PiecewiseMap> for Mapper {
+    fn from(mapper: PiecewiseMap) -> Self {
+        Mapper::Piecewise(mapper)
+    }
+}
+
+impl From<Mapper> for NormalMap {
+    fn from(mapper: Mapper) -> Self {
+        NormalMap::from_mapper(mapper)
+    }
+}
+
+fn assert_window(window: (f32, f32)) {
+    assert!(0.0 <= window.0 && window.0 < window.1 && window.1 <= 1.0);
+}
+
+/// Snap a normalized value to the nearest of `bins` evenly spaced levels.
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn quantize(normalized: f32, bins: usize) -> f32 {
+    assert!(bins > 0);
+
+    if bins == 1 {
+        return 0.0;
+    }
+
+    let steps = (bins - 1) as f32;
+
+    (normalized * steps).round() / steps
+}
+
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn flush_subnormal(value: f32) -> f32 {
+    if value.is_subnormal() {
+        0.0
+    } else {
+        value
+    }
+}
+
+/// Returns `Some(min)` when a normalized value is at or below `0.0`, `Some(max)`
+/// when it is at or above `1.0`, or `None` when it is inside of the normalized
+/// range and needs to be mapped.
+///
+/// This is the boundary check shared by the denormalize methods of the mappers.
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn clamp_normalized<T>(normalized: f32, min: T, max: T) -> Option<T> {
+    if normalized <= 0.0 {
+        Some(min)
+    } else if normalized >= 1.0 {
+        Some(max)
+    } else {
+        None
+    }
+}
+
+/// Returns `Some(0.0)` when a value is at or below `min`, `Some(1.0)` when it
+/// is at or above `max`, or `None` when it is inside of the range and needs to
+/// be mapped.
+///
+/// This is the boundary check shared by the normalize methods of the mappers.
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn clamp_value(value: f32, min: f32, max: f32) -> Option<f32> {
+    if value <= min {
+        Some(0.0)
+    } else if value >= max {
+        Some(1.0)
+    } else {
+        None
+    }
+}
+
+/// The normalized position of sample `i` out of `count` evenly spaced samples,
+/// where the first is exactly `0.0` and the last is exactly `1.0`.
+pub(crate) fn sample_position(i: usize, count: usize) -> f32 {
+    if count <= 1 {
+        0.0
+    } else {
+        i as f32 / (count - 1) as f32
+    }
+}
+
+fn check_lengths(input_len: usize, output_len: usize) -> Result<(), LengthMismatch> {
+    if input_len == output_len {
+        Ok(())
+    } else {
+        Err(LengthMismatch {
+            input_len,
+            output_len,
+        })
+    }
 }
@@ -0,0 +1,66 @@
+use std::convert::TryFrom;
+use std::fmt::Debug;
+
+use crate::NotNormalized;
+
+/// An `f32` value that is guaranteed to be in the normalized range `[0.0, 1.0]`.
+///
+/// Using this in a signature makes it explicit that a normalized value is
+/// expected, which prevents passing a raw value by mistake. Use
+/// [`NormalMap::denormalize_checked`](super::NormalMap::denormalize_checked)
+/// to un-map it.
+///
+/// # Example
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// # use normal_map::f32::Normalized;
+/// assert_eq!(0.25, Normalized::try_from(0.25).unwrap().get());
+/// assert!(Normalized::try_from(25.0).is_err());
+/// assert_eq!(1.0, Normalized::new_clamped(25.0).get());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Normalized(f32);
+
+impl Normalized {
+    /// The normalized value `0.0`.
+    pub const ZERO: Normalized = Normalized(0.0);
+    /// The normalized value `1.0`.
+    pub const ONE: Normalized = Normalized(1.0);
+
+    /// Create a new `Normalized` by clamping `value` to the range `[0.0, 1.0]`.
+    ///
+    /// NaN is mapped to `0.0`.
+    pub fn new_clamped(value: f32) -> Self {
+        if value.is_nan() {
+            return Self::ZERO;
+        }
+
+        Self(value.clamp(0.0, 1.0))
+    }
+
+    /// The normalized value.
+    pub fn get(self) -> f32 {
+        self.0
+    }
+}
+
+impl TryFrom<f32> for Normalized {
+    type Error = NotNormalized;
+
+    /// Returns an error if `value` is not in the range `[0.0, 1.0]`, including
+    /// when it is NaN.
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(NotNormalized);
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl From<Normalized> for f32 {
+    fn from(normalized: Normalized) -> Self {
+        normalized.0
+    }
+}
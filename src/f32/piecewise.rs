@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+use super::{clamp_normalized, clamp_value};
+use crate::NotMonotonic;
+
+/// Mapping that linearly interpolates between raw values sampled at evenly
+/// spaced normalized positions.
+///
+/// This is useful for reproducing the measured response curve of a hardware
+/// control.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiecewiseMap {
+    samples: Vec<f32>,
+    last: usize,
+    last_inv: f32,
+}
+
+impl PiecewiseMap {
+    /// Create a new `PiecewiseMap` where `raw_values` are the denormalized
+    /// values at the evenly spaced normalized positions
+    /// `0, 1/(n-1), 2/(n-1), ..., 1`.
+    ///
+    /// The samples must be strictly increasing so that the map can be inverted.
+    ///
+    /// # Arguments
+    ///
+    /// * raw_values - the raw value at each normalized position
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error with the index of the first sample that is not
+    ///   greater than the one before it.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when there are fewer than 2 samples.
+    pub fn from_samples(raw_values: &[f32]) -> Result<Self, NotMonotonic> {
+        assert!(raw_values.len() >= 2);
+
+        for i in 1..raw_values.len() {
+            // NaN samples are incomparable, so they are rejected as well.
+            if raw_values[i].partial_cmp(&raw_values[i - 1]) != Some(Ordering::Greater) {
+                return Err(NotMonotonic { index: i });
+            }
+        }
+
+        Ok(Self::from_samples_unchecked(raw_values.to_vec()))
+    }
+
+    fn from_samples_unchecked(samples: Vec<f32>) -> Self {
+        let last = samples.len() - 1;
+
+        Self {
+            samples,
+            last,
+            last_inv: 1.0 / last as f32,
+        }
+    }
+
+    /// Change the range in place by linearly rescaling every sample, keeping
+    /// the same shape.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the new minimum of the range
+    /// * max - the new maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        assert!(min <= max);
+
+        let old_min = self.min();
+        let scale = if self.is_degenerate() {
+            0.0
+        } else {
+            (max - min) / (self.max() - old_min)
+        };
+
+        let samples = self
+            .samples
+            .iter()
+            .map(|&sample| min + ((sample - old_min) * scale))
+            .collect();
+
+        *self = Self::from_samples_unchecked(samples);
+    }
+
+    /// The raw value at each evenly spaced normalized position.
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// The minimum of the range.
+    pub fn min(&self) -> f32 {
+        self.samples[0]
+    }
+
+    /// The maximum of the range.
+    pub fn max(&self) -> f32 {
+        self.samples[self.last]
+    }
+
+    /// The width of the range, `max - min`.
+    pub fn range(&self) -> f32 {
+        self.max() - self.min()
+    }
+
+    /// Returns `true` if the range has zero width, meaning every value
+    /// maps to the same normalized value.
+    pub fn is_degenerate(&self) -> bool {
+        self.max() - self.min() == 0.0
+    }
+
+    /// Clamp an `f32` value to the range of the map without normalizing it.
+    pub fn clamp(&self, value: f32) -> f32 {
+        value.max(self.min()).min(self.max())
+    }
+
+    /// Format an `f32` value for display with the given number of decimal places.
+    pub fn format_value(&self, value: f32, decimals: usize) -> String {
+        format!("{:.*}", decimals, value)
+    }
+
+    /// Map an `f32` value to the normalized range `[0.0, 1.0]`.
+    pub fn normalize(&self, value: f32) -> f32 {
+        debug_assert!(value.is_finite());
+        self.normalize_generic(value)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn normalize_generic(&self, value: f32) -> f32 {
+        if let Some(normalized) = clamp_value(value, self.min(), self.max()) {
+            return normalized;
+        }
+
+        let i = self.samples.partition_point(|&sample| sample <= value) - 1;
+        let segment = self.samples[i + 1] - self.samples[i];
+
+        (i as f32 + ((value - self.samples[i]) / segment)) * self.last_inv
+    }
+
+    /// Map an array of `f32` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[f32], out_normalized: &mut [f32]) {
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        let input = &in_values[..min_len];
+        let output = &mut out_normalized[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.normalize_generic(input[i]);
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding `f32` value.
+    pub fn denormalize(&self, normalized: f32) -> f32 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
+        self.denormalize_generic(normalized)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn denormalize_generic(&self, normalized: f32) -> f32 {
+        if let Some(bound) = clamp_normalized(normalized, self.min(), self.max()) {
+            return bound;
+        }
+
+        let position = normalized * self.last as f32;
+        let i = (position as usize).min(self.last - 1);
+        let blend = position - i as f32;
+
+        self.samples[i] + ((self.samples[i + 1] - self.samples[i]) * blend)
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f32` value.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[f32], out_values: &mut [f32]) {
+        self.denormalize_array_with(in_normalized, out_values, |value| value);
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub(super) fn denormalize_array_with<F: Fn(f32) -> f32>(
+        &self,
+        in_normalized: &[f32],
+        out_values: &mut [f32],
+        post: F,
+    ) {
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        let input = &in_normalized[..min_len];
+        let output = &mut out_values[..min_len];
+
+        for i in 0..min_len {
+            output[i] = post(self.denormalize_generic(input[i]));
+        }
+    }
+}
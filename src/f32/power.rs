@@ -1,5 +1,6 @@
 use super::linear_base;
 use super::Unit;
+use super::{clamp_normalized, clamp_value};
 
 use std::fmt::Debug;
 
@@ -8,13 +9,23 @@ use std::fmt::Debug;
 ///
 /// Please note if you use `Unit::Decibels`, then the decibels
 /// are what will be mapped, not the raw amplitude.
-#[derive(Debug)]
+///
+/// The exponent is applied in normalized space, where the base is always in
+/// `[0.0, 1.0]`. This means ranges that span negative values (such as `-100.0`
+/// to `100.0`) are always safe, even with fractional exponents.
+///
+/// A map created with [`PowerMap::new_raw_exponent`] instead raises the raw
+/// value to the exponent before linear normalization.
+#[derive(Debug, Clone, PartialEq)]
 pub struct PowerMap {
     lin_base: linear_base::Base,
     min: f32,
     max: f32,
+    min_value: f32,
+    max_value: f32,
     exponent: f32,
     exponent_inv: f32,
+    raw_exponent: bool,
 }
 
 impl PowerMap {
@@ -24,6 +35,9 @@ impl PowerMap {
     /// Please note if you use `Unit::Decibels`, then the decibels
     /// are what will be mapped, not the raw amplitude.
     ///
+    /// The exponent is applied in normalized space, so `min` and `max` may be
+    /// negative even with fractional exponents.
+    ///
     /// # Arguments
     ///
     /// * min - the minimum of the range
@@ -33,70 +47,221 @@ impl PowerMap {
     ///
     /// # Panics
     ///
-    /// * Panics when `exponent = 0.0`.
+    /// * Panics when `exponent <= 0.0` or `exponent` is NaN.
+    /// * Panics when `exponent` or `1.0 / exponent` is not finite.
+    /// * Panics when `min > max`.
     pub fn new(min: f32, max: f32, exponent: f32, unit: Unit) -> Self {
-        if exponent <= 0.0 {
-            panic!("Exponent cannot be 0");
-        }
-
         let exponent_inv = 1.0 / exponent;
+        assert!(
+            exponent > 0.0 && exponent.is_finite() && exponent_inv.is_finite(),
+            "exponent must be positive and finite, with a finite reciprocal"
+        );
+        assert!(min <= max);
 
         let lin_base = linear_base::Base::new(min, max, unit);
+        let (min_value, max_value) = lin_base.value_bounds(min, max);
 
         Self {
             lin_base,
             min,
             max,
+            min_value,
+            max_value,
             exponent,
             exponent_inv,
+            raw_exponent: false,
+        }
+    }
+
+    /// Create a new `PowerMap` where the raw value is raised to the supplied
+    /// exponent before being linearly normalized.
+    ///
+    /// This differs from [`PowerMap::new`], which linearly normalizes the value
+    /// first and then applies the exponent to the normalized value. Here the
+    /// mapping is `normalize(v) = (v^exponent - min^exponent) / (max^exponent - min^exponent)`,
+    /// which is useful for gamma-like corrections on already-positive data.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    /// * exponent - the exponent to raise the raw value to
+    /// * unit - the type of unit
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `exponent <= 0.0` or `exponent` is NaN.
+    /// * Panics when `min < 0.0` or `max < 0.0`.
+    /// * Panics when `min > max`.
+    /// * Panics when `exponent` or `1.0 / exponent` is not finite, or when
+    ///   `max^exponent` overflows to infinity.
+    /// * Panics when `unit` is `Unit::Decibels`.
+    pub fn new_raw_exponent(min: f32, max: f32, exponent: f32, unit: Unit) -> Self {
+        assert!(
+            exponent > 0.0 && exponent.is_finite() && (1.0 / exponent).is_finite(),
+            "exponent must be positive and finite, with a finite reciprocal"
+        );
+        assert!(max.powf(exponent).is_finite());
+        assert!(min >= 0.0 && max >= 0.0);
+        assert!(min <= max);
+        assert!(!matches!(unit, Unit::Decibels { .. }));
+
+        let lin_base = linear_base::Base::new(min.powf(exponent), max.powf(exponent), unit);
+
+        Self {
+            lin_base,
+            min,
+            max,
+            min_value: min,
+            max_value: max,
+            exponent,
+            exponent_inv: 1.0 / exponent,
+            raw_exponent: true,
         }
     }
 
+    /// Change the range in place, keeping the same exponent and unit.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the new minimum of the range
+    /// * max - the new maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when the exponent is applied to the raw value and either
+    ///   `min` or `max` < 0.0.
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        *self = if self.raw_exponent {
+            Self::new_raw_exponent(min, max, self.exponent, self.unit())
+        } else {
+            Self::new(min, max, self.exponent, self.unit())
+        };
+    }
+
+    /// The minimum of the range.
+    ///
+    /// For `Unit::Decibels`, this is in decibels.
+    pub fn min(&self) -> f32 {
+        self.min
+    }
+
+    /// The maximum of the range.
+    ///
+    /// For `Unit::Decibels`, this is in decibels.
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// The width of the range, `max - min`.
+    ///
+    /// For `Unit::Decibels`, this is in decibels.
+    pub fn range(&self) -> f32 {
+        self.max - self.min
+    }
+
+    /// The exponent the normalized value (or the raw value, see
+    /// [`PowerMap::is_raw_exponent`]) is raised to.
+    pub fn exponent(&self) -> f32 {
+        self.exponent
+    }
+
+    /// Returns `true` if the exponent is applied to the raw value rather
+    /// than the normalized value.
+    pub fn is_raw_exponent(&self) -> bool {
+        self.raw_exponent
+    }
+
+    /// The type of unit.
+    pub fn unit(&self) -> Unit {
+        self.lin_base.unit()
+    }
+
+    /// The `neg_infinity_clamp` in decibels, or `None` if the unit is not
+    /// `Unit::Decibels` with a `neg_infinity_clamp`.
+    pub fn clamp_db(&self) -> Option<f32> {
+        self.lin_base.clamp_db()
+    }
+
+    /// The raw amplitude at or below which values are clamped to silence, or
+    /// `None` if the unit is not `Unit::Decibels` with a `neg_infinity_clamp`.
+    ///
+    /// This is useful for drawing the clamp threshold on a meter.
+    pub fn clamp_coeff(&self) -> Option<f32> {
+        self.lin_base.clamp_coeff()
+    }
+
+    /// Returns `true` if the range has zero width, meaning every value
+    /// maps to the same normalized value.
+    pub fn is_degenerate(&self) -> bool {
+        self.max - self.min == 0.0
+    }
+
+    /// Clamp an `f32` value to the range of the map without normalizing it.
+    ///
+    /// For `Unit::Decibels`, the value is a raw amplitude and is clamped to
+    /// the amplitudes of the range.
+    pub fn clamp(&self, value: f32) -> f32 {
+        value.max(self.min_value).min(self.max_value)
+    }
+
+    /// Format an `f32` value for display with the given number of decimal places.
+    ///
+    /// For `Unit::Decibels`, the raw amplitude is displayed in decibels, and
+    /// amplitudes at or below the `neg_infinity_clamp` are displayed as `-inf dB`.
+    /// For `Unit::Frequency`, values are displayed in Hz, or kHz at and above 1000 Hz.
+    /// For `Unit::Percent`, values are displayed with a `%` suffix.
+    pub fn format_value(&self, value: f32, decimals: usize) -> String {
+        self.lin_base.format_value(value, decimals)
+    }
+
     /// Map an `f32` value to the normalized range `[0.0, 1.0]`.
     pub fn normalize(&self, value: f32) -> f32 {
+        debug_assert!(value.is_finite());
+
         match &self.lin_base {
-            linear_base::Base::Generic(base) => self.normalize_generic(value, &base),
-            linear_base::Base::DB(base) => self.normalize_db(value, &base),
-            linear_base::Base::DBClamped(base) => self.normalize_db_clamped(value, &base),
+            linear_base::Base::Generic(base)
+            | linear_base::Base::Frequency(base)
+            | linear_base::Base::Percent(base) => self.normalize_generic(value, base),
+            linear_base::Base::GenericConst(base) => {
+                self.normalize_generic(value, &base.to_generic())
+            }
+            linear_base::Base::DB(base) => self.normalize_db(value, base),
+            linear_base::Base::DBClamped(base) => self.normalize_db_clamped(value, base),
         }
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn normalize_generic(&self, value: f32, lin_base: &linear_base::Generic) -> f32 {
-        if value <= self.min {
-            return 0.0;
-        };
-        if value >= self.max {
-            return 1.0;
-        };
+        if let Some(normalized) = clamp_value(value, self.min_value, self.max_value) {
+            return normalized;
+        }
+
+        if self.raw_exponent {
+            return lin_base.normalize(value.powf(self.exponent));
+        }
 
         let lin_mapped = lin_base.normalize(value);
 
         lin_mapped.powf(self.exponent_inv)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn normalize_db(&self, value: f32, lin_base: &linear_base::DB) -> f32 {
-        if value <= self.min {
-            return 0.0;
-        };
-        if value >= self.max {
-            return 1.0;
-        };
+        if let Some(normalized) = clamp_value(value, self.min_value, self.max_value) {
+            return normalized;
+        }
 
         let lin_mapped = lin_base.normalize(value);
 
         lin_mapped.powf(self.exponent_inv)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn normalize_db_clamped(&self, value: f32, lin_base: &linear_base::DBClamped) -> f32 {
-        if value <= self.min {
-            return 0.0;
-        };
-        if value >= self.max {
-            return 1.0;
-        };
+        if let Some(normalized) = clamp_value(value, self.min_value, self.max_value) {
+            return normalized;
+        }
 
         let lin_mapped = lin_base.normalize(value);
 
@@ -112,40 +277,88 @@ impl PowerMap {
         let output = &mut out_normalized[..min_len];
 
         match &self.lin_base {
-            linear_base::Base::Generic(base) => {
+            linear_base::Base::Generic(base)
+            | linear_base::Base::Frequency(base)
+            | linear_base::Base::Percent(base) => {
+                for i in 0..min_len {
+                    output[i] = self.normalize_generic(input[i], base);
+                }
+            }
+            linear_base::Base::GenericConst(base) => {
+                let base = base.to_generic();
                 for i in 0..min_len {
                     output[i] = self.normalize_generic(input[i], &base);
                 }
             }
             linear_base::Base::DB(base) => {
                 for i in 0..min_len {
-                    output[i] = self.normalize_db(input[i], &base);
+                    output[i] = self.normalize_db(input[i], base);
                 }
             }
             linear_base::Base::DBClamped(base) => {
                 for i in 0..min_len {
-                    output[i] = self.normalize_db_clamped(input[i], &base);
+                    output[i] = self.normalize_db_clamped(input[i], base);
                 }
             }
         }
     }
 
+    /// Map a raw amplitude to the normalized range `[0.0, 1.0]`.
+    ///
+    /// This is the same as `normalize`, but states that the input is an
+    /// amplitude and not decibels.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when the unit is not `Unit::Decibels`.
+    pub fn amp_to_normalized(&self, amplitude: f32) -> f32 {
+        assert!(
+            matches!(self.unit(), Unit::Decibels { .. }),
+            "amp_to_normalized requires a map with `Unit::Decibels`"
+        );
+
+        self.normalize(amplitude)
+    }
+
+    /// Map a value in decibels to the normalized range `[0.0, 1.0]`, converting
+    /// it to a raw amplitude first.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when the unit is not `Unit::Decibels`.
+    pub fn db_to_normalized(&self, db: f32) -> f32 {
+        assert!(
+            matches!(self.unit(), Unit::Decibels { .. }),
+            "db_to_normalized requires a map with `Unit::Decibels`"
+        );
+
+        self.normalize(linear_base::db_to_coeff(db))
+    }
+
     /// Un-map a normalized value to the corresponding `f32` value.
     pub fn denormalize(&self, normalized: f32) -> f32 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
+
         match &self.lin_base {
-            linear_base::Base::Generic(base) => self.denormalize_generic(normalized, &base),
-            linear_base::Base::DB(base) => self.denormalize_db(normalized, &base),
-            linear_base::Base::DBClamped(base) => self.denormalize_db_clamped(normalized, &base),
+            linear_base::Base::Generic(base)
+            | linear_base::Base::Frequency(base)
+            | linear_base::Base::Percent(base) => self.denormalize_generic(normalized, base),
+            linear_base::Base::GenericConst(base) => {
+                self.denormalize_generic(normalized, &base.to_generic())
+            }
+            linear_base::Base::DB(base) => self.denormalize_db(normalized, base),
+            linear_base::Base::DBClamped(base) => self.denormalize_db_clamped(normalized, base),
         }
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn denormalize_generic(&self, normalized: f32, lin_base: &linear_base::Generic) -> f32 {
-        if normalized <= 0.0 {
-            return self.min;
+        if let Some(bound) = clamp_normalized(normalized, self.min_value, self.max_value) {
+            return bound;
         }
-        if normalized >= 1.0 {
-            return self.max;
+
+        if self.raw_exponent {
+            return lin_base.denormalize(normalized).powf(self.exponent_inv);
         }
 
         let value = normalized.powf(self.exponent);
@@ -153,13 +366,10 @@ impl PowerMap {
         lin_base.denormalize(value)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn denormalize_db(&self, normalized: f32, lin_base: &linear_base::DB) -> f32 {
-        if normalized <= 0.0 {
-            return self.min;
-        }
-        if normalized >= 1.0 {
-            return self.max;
+        if let Some(bound) = clamp_normalized(normalized, self.min_value, self.max_value) {
+            return bound;
         }
 
         let value = normalized.powf(self.exponent);
@@ -167,13 +377,10 @@ impl PowerMap {
         lin_base.denormalize(value)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn denormalize_db_clamped(&self, normalized: f32, lin_base: &linear_base::DBClamped) -> f32 {
-        if normalized <= 0.0 {
-            return self.min;
-        }
-        if normalized >= 1.0 {
-            return self.max;
+        if let Some(bound) = clamp_normalized(normalized, self.min_value, self.max_value) {
+            return bound;
         }
 
         let value = normalized.powf(self.exponent);
@@ -185,24 +392,42 @@ impl PowerMap {
     ///
     /// Values will be processed up to the length of the shortest array.
     pub fn denormalize_array(&self, in_normalized: &[f32], out_values: &mut [f32]) {
+        self.denormalize_array_with(in_normalized, out_values, |value| value);
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub(super) fn denormalize_array_with<F: Fn(f32) -> f32>(
+        &self,
+        in_normalized: &[f32],
+        out_values: &mut [f32],
+        post: F,
+    ) {
         let min_len = std::cmp::min(in_normalized.len(), out_values.len());
         let input = &in_normalized[..min_len];
         let output = &mut out_values[..min_len];
 
         match &self.lin_base {
-            linear_base::Base::Generic(base) => {
+            linear_base::Base::Generic(base)
+            | linear_base::Base::Frequency(base)
+            | linear_base::Base::Percent(base) => {
+                for i in 0..min_len {
+                    output[i] = post(self.denormalize_generic(input[i], base));
+                }
+            }
+            linear_base::Base::GenericConst(base) => {
+                let base = base.to_generic();
                 for i in 0..min_len {
-                    output[i] = self.denormalize_generic(input[i], &base);
+                    output[i] = post(self.denormalize_generic(input[i], &base));
                 }
             }
             linear_base::Base::DB(base) => {
                 for i in 0..min_len {
-                    output[i] = self.denormalize_db(input[i], &base);
+                    output[i] = post(self.denormalize_db(input[i], base));
                 }
             }
             linear_base::Base::DBClamped(base) => {
                 for i in 0..min_len {
-                    output[i] = self.denormalize_db_clamped(input[i], &base);
+                    output[i] = post(self.denormalize_db_clamped(input[i], base));
                 }
             }
         }
@@ -0,0 +1,155 @@
+use std::fmt::Debug;
+
+use super::linear_base;
+
+/// Linear mapping for cyclic values, such as a phase in degrees.
+///
+/// Values outside of the range wrap around instead of being clamped, so
+/// `max` is the same point as `min`, and both `0.0` and `1.0` denormalize
+/// to `min`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrappingMap {
+    min: f32,
+    max: f32,
+    lin_base: linear_base::Generic,
+}
+
+impl WrappingMap {
+    /// Create a new `WrappingMap` for linear mapping of cyclic values.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the start of the cycle
+    /// * max - the end of the cycle, which is the same point as `min`
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    pub fn new(min: f32, max: f32) -> Self {
+        assert!(min <= max);
+
+        Self {
+            min,
+            max,
+            lin_base: linear_base::Generic::new(min, max),
+        }
+    }
+
+    /// Change the range in place.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the new start of the cycle
+    /// * max - the new end of the cycle
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        *self = Self::new(min, max);
+    }
+
+    /// The start of the cycle.
+    pub fn min(&self) -> f32 {
+        self.min
+    }
+
+    /// The end of the cycle.
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// The width of the range, `max - min`.
+    pub fn range(&self) -> f32 {
+        self.max - self.min
+    }
+
+    /// Returns `true` if the range has zero width, meaning every value
+    /// maps to the same normalized value.
+    pub fn is_degenerate(&self) -> bool {
+        self.max - self.min == 0.0
+    }
+
+    /// Wrap an `f32` value into the range `[min, max)` without normalizing it.
+    pub fn clamp(&self, value: f32) -> f32 {
+        self.lin_base.denormalize(self.normalize_generic(value))
+    }
+
+    /// Format an `f32` value for display with the given number of decimal places.
+    pub fn format_value(&self, value: f32, decimals: usize) -> String {
+        format!("{:.*}", decimals, value)
+    }
+
+    /// Map an `f32` value to the normalized range `[0.0, 1.0)`, wrapping values
+    /// outside of the range around.
+    pub fn normalize(&self, value: f32) -> f32 {
+        debug_assert!(value.is_finite());
+        self.normalize_generic(value)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn normalize_generic(&self, value: f32) -> f32 {
+        wrap_normalized(self.lin_base.normalize(value))
+    }
+
+    /// Map an array of `f32` values to the normalized range `[0.0, 1.0)`,
+    /// wrapping values outside of the range around.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[f32], out_normalized: &mut [f32]) {
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        let input = &in_values[..min_len];
+        let output = &mut out_normalized[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.normalize_generic(input[i]);
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding `f32` value.
+    ///
+    /// Both `0.0` and `1.0` denormalize to `min`.
+    pub fn denormalize(&self, normalized: f32) -> f32 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
+        self.denormalize_generic(normalized)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn denormalize_generic(&self, normalized: f32) -> f32 {
+        self.lin_base.denormalize(wrap_normalized(normalized))
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f32` value.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[f32], out_values: &mut [f32]) {
+        self.denormalize_array_with(in_normalized, out_values, |value| value);
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub(super) fn denormalize_array_with<F: Fn(f32) -> f32>(
+        &self,
+        in_normalized: &[f32],
+        out_values: &mut [f32],
+        post: F,
+    ) {
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        let input = &in_normalized[..min_len];
+        let output = &mut out_values[..min_len];
+
+        for i in 0..min_len {
+            output[i] = post(self.denormalize_generic(input[i]));
+        }
+    }
+}
+
+/// Wrap a normalized value into the range `[0.0, 1.0)`.
+///
+/// For a tiny negative value, `normalized - normalized.floor()` rounds up to
+/// exactly `1.0`, which wraps to `0.0` instead.
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn wrap_normalized(normalized: f32) -> f32 {
+    let wrapped = normalized - normalized.floor();
+
+    if wrapped >= 1.0 {
+        0.0
+    } else {
+        wrapped
+    }
+}
@@ -0,0 +1,104 @@
+use approx::{AbsDiffEq, RelativeEq};
+
+use super::{Mapper, NormalMap, Unit};
+
+/// Compares the parameters the maps were constructed with (such as the
+/// range, exponent, unit, margin, and dead zone). Maps using different
+/// mappers are never equal.
+impl AbsDiffEq for NormalMap {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        f64::EPSILON
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+        let eq = |a: f64, b: f64| a.abs_diff_eq(&b, epsilon);
+
+        params_eq(&self.mapper, &other.mapper, eq)
+            && eq(self.margin, other.margin)
+            && eq(self.dead_zone, other.dead_zone)
+    }
+}
+
+/// Compares the parameters the maps were constructed with (such as the
+/// range, exponent, unit, margin, and dead zone). Maps using different
+/// mappers are never equal.
+impl RelativeEq for NormalMap {
+    fn default_max_relative() -> f64 {
+        f64::EPSILON
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+        let eq = |a: f64, b: f64| a.relative_eq(&b, epsilon, max_relative);
+
+        params_eq(&self.mapper, &other.mapper, eq)
+            && eq(self.margin, other.margin)
+            && eq(self.dead_zone, other.dead_zone)
+    }
+}
+
+fn params_eq<F: Fn(f64, f64) -> bool>(a: &Mapper, b: &Mapper, eq: F) -> bool {
+    match (a, b) {
+        (Mapper::Lin(a), Mapper::Lin(b)) => {
+            eq(a.min(), b.min()) && eq(a.max(), b.max()) && unit_eq(a.unit(), b.unit(), &eq)
+        }
+        (Mapper::Pow(a), Mapper::Pow(b)) => {
+            eq(a.min(), b.min())
+                && eq(a.max(), b.max())
+                && eq(a.exponent(), b.exponent())
+                && a.is_raw_exponent() == b.is_raw_exponent()
+                && unit_eq(a.unit(), b.unit(), &eq)
+        }
+        (Mapper::Log2(a), Mapper::Log2(b)) => {
+            eq(a.min(), b.min())
+                && eq(a.max(), b.max())
+                && a.unit() == b.unit()
+                && a.is_octave_snapped() == b.is_octave_snapped()
+        }
+        (Mapper::Discrete(a), Mapper::Discrete(b)) => {
+            a.min() == b.min()
+                && a.max() == b.max()
+                && a.is_reversed() == b.is_reversed()
+                && a.groups() == b.groups()
+                && a.rounding_mode() == b.rounding_mode()
+        }
+        (Mapper::Bilinear(a), Mapper::Bilinear(b)) => {
+            eq(a.min(), b.min())
+                && eq(a.max(), b.max())
+                && eq(a.center_raw(), b.center_raw())
+                && eq(a.center_norm(), b.center_norm())
+        }
+        (Mapper::Stepped(a), Mapper::Stepped(b)) => {
+            eq(a.min(), b.min()) && eq(a.max(), b.max()) && eq(a.step(), b.step())
+        }
+        (Mapper::Smoothstep(a), Mapper::Smoothstep(b)) => {
+            eq(a.min(), b.min()) && eq(a.max(), b.max()) && a.order() == b.order()
+        }
+        (Mapper::Note(a), Mapper::Note(b)) => {
+            eq(a.ref_a4_hz(), b.ref_a4_hz())
+                && a.min_note() == b.min_note()
+                && a.max_note() == b.max_note()
+        }
+        (Mapper::Wrapping(a), Mapper::Wrapping(b)) => eq(a.min(), b.min()) && eq(a.max(), b.max()),
+        (Mapper::Piecewise(a), Mapper::Piecewise(b)) => {
+            a.samples().len() == b.samples().len()
+                && a.samples().iter().zip(b.samples()).all(|(&a, &b)| eq(a, b))
+        }
+        _ => false,
+    }
+}
+
+fn unit_eq<F: Fn(f64, f64) -> bool>(a: Unit, b: Unit, eq: &F) -> bool {
+    match (a, b) {
+        (
+            Unit::Decibels {
+                neg_infinity_clamp: Some(a),
+            },
+            Unit::Decibels {
+                neg_infinity_clamp: Some(b),
+            },
+        ) => eq(a, b),
+        (a, b) => a == b,
+    }
+}
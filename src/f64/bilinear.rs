@@ -0,0 +1,183 @@
+use std::fmt::Debug;
+
+use super::linear_base;
+use super::{clamp_normalized, clamp_value};
+
+/// Two-segment linear mapping with a breakpoint.
+///
+/// The range `[min, center_raw]` is linearly mapped to `[0.0, center_norm]`,
+/// and the range `[center_raw, max]` is linearly mapped to `[center_norm, 1.0]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BilinearMap {
+    min: f64,
+    max: f64,
+    center_raw: f64,
+    center_norm: f64,
+    center_norm_inv: f64,
+    upper_norm_inv: f64,
+    lower_base: linear_base::Generic,
+    upper_base: linear_base::Generic,
+}
+
+impl BilinearMap {
+    /// Create a new `BilinearMap` for two-segment linear mapping.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * center_raw - the value at the breakpoint
+    /// * center_norm - the normalized position of the breakpoint
+    /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min < center_raw < max` does not hold.
+    /// * Panics when `0.0 < center_norm < 1.0` does not hold.
+    pub fn new(min: f64, center_raw: f64, center_norm: f64, max: f64) -> Self {
+        assert!(min < center_raw && center_raw < max);
+        assert!(center_norm > 0.0 && center_norm < 1.0);
+
+        Self {
+            min,
+            max,
+            center_raw,
+            center_norm,
+            center_norm_inv: 1.0 / center_norm,
+            upper_norm_inv: 1.0 / (1.0 - center_norm),
+            lower_base: linear_base::Generic::new(min, center_raw),
+            upper_base: linear_base::Generic::new(center_raw, max),
+        }
+    }
+
+    /// Change the range in place, keeping the same breakpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the new minimum of the range
+    /// * max - the new maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min < center_raw < max` does not hold.
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        *self = Self::new(min, self.center_raw, self.center_norm, max);
+    }
+
+    /// The minimum of the range.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// The maximum of the range.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// The width of the range, `max - min`.
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// The value at the breakpoint.
+    pub fn center_raw(&self) -> f64 {
+        self.center_raw
+    }
+
+    /// The normalized position of the breakpoint.
+    pub fn center_norm(&self) -> f64 {
+        self.center_norm
+    }
+
+    /// Returns `true` if the range has zero width.
+    ///
+    /// This is always `false`, since the constructor rejects such ranges.
+    pub fn is_degenerate(&self) -> bool {
+        false
+    }
+
+    /// Clamp an `f64` value to the range of the map without normalizing it.
+    pub fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+
+    /// Format an `f64` value for display with the given number of decimal places.
+    pub fn format_value(&self, value: f64, decimals: usize) -> String {
+        format!("{:.*}", decimals, value)
+    }
+
+    /// Map an `f64` value to the normalized range `[0.0, 1.0]`.
+    pub fn normalize(&self, value: f64) -> f64 {
+        debug_assert!(value.is_finite());
+        self.normalize_generic(value)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn normalize_generic(&self, value: f64) -> f64 {
+        if let Some(normalized) = clamp_value(value, self.min, self.max) {
+            return normalized;
+        }
+
+        if value < self.center_raw {
+            self.lower_base.normalize(value) * self.center_norm
+        } else {
+            self.center_norm + (self.upper_base.normalize(value) * (1.0 - self.center_norm))
+        }
+    }
+
+    /// Map an array of `f64` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[f64], out_normalized: &mut [f64]) {
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        let input = &in_values[..min_len];
+        let output = &mut out_normalized[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.normalize_generic(input[i]);
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding `f64` value.
+    pub fn denormalize(&self, normalized: f64) -> f64 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
+        self.denormalize_generic(normalized)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn denormalize_generic(&self, normalized: f64) -> f64 {
+        if let Some(bound) = clamp_normalized(normalized, self.min, self.max) {
+            return bound;
+        }
+
+        if normalized < self.center_norm {
+            self.lower_base
+                .denormalize(normalized * self.center_norm_inv)
+        } else {
+            self.upper_base
+                .denormalize((normalized - self.center_norm) * self.upper_norm_inv)
+        }
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f64` value.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[f64], out_values: &mut [f64]) {
+        self.denormalize_array_with(in_normalized, out_values, |value| value);
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub(super) fn denormalize_array_with<F: Fn(f64) -> f64>(
+        &self,
+        in_normalized: &[f64],
+        out_values: &mut [f64],
+        post: F,
+    ) {
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        let input = &in_normalized[..min_len];
+        let output = &mut out_values[..min_len];
+
+        for i in 0..min_len {
+            output[i] = post(self.denormalize_generic(input[i]));
+        }
+    }
+}
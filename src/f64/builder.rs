@@ -0,0 +1,150 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+use super::{CurveKind, NormalMap, Unit};
+use crate::BuildError;
+
+/// A builder for a `NormalMap` that checks the combination of its settings,
+/// returning an error instead of panicking or producing a surprising map.
+///
+/// This catches mistakes that the individual constructors can't, because
+/// they only see their own arguments, such as `Unit::Decibels` on a `log2`
+/// curve.
+///
+/// Only `CurveKind::Linear`, `CurveKind::Power`, and `CurveKind::Log2` are
+/// supported.
+///
+/// # Example
+///
+/// ```
+/// # use normal_map::f64::{CurveKind, NormalMapBuilder, Unit};
+/// # use normal_map::BuildError;
+/// let cutoff = NormalMapBuilder::new(CurveKind::Log2, 20.0, 20480.0)
+///     .unit(Unit::Frequency)
+///     .build()
+///     .unwrap();
+///
+/// let gain = NormalMapBuilder::new(CurveKind::Log2, -90.0, 6.0)
+///     .unit(Unit::Decibels { neg_infinity_clamp: Some(-90.0) })
+///     .build();
+///
+/// assert_eq!(Err(BuildError::DecibelsOnLog2), gain);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalMapBuilder {
+    kind: CurveKind,
+    min: f64,
+    max: f64,
+    exponent: Option<f64>,
+    unit: Unit,
+}
+
+impl NormalMapBuilder {
+    /// Create a new `NormalMapBuilder` using `Unit::Generic` and no exponent.
+    ///
+    /// # Arguments
+    ///
+    /// * kind - the kind of curve
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    pub fn new(kind: CurveKind, min: f64, max: f64) -> Self {
+        Self {
+            kind,
+            min,
+            max,
+            exponent: None,
+            unit: Unit::Generic,
+        }
+    }
+
+    /// Set the exponent, which is required by `CurveKind::Power` and not
+    /// allowed for any other kind of curve.
+    pub fn exponent(mut self, exponent: f64) -> Self {
+        self.exponent = Some(exponent);
+        self
+    }
+
+    /// Set the type of unit.
+    ///
+    /// For `Unit::Decibels`, `min` and `max` are in decibels.
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Check the settings and build the `NormalMap`.
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error when `min > max`.
+    /// * Returns an error when the exponent is missing for `CurveKind::Power`,
+    ///   or set for any other kind of curve.
+    /// * Returns an error when the exponent is not positive, or when it or its
+    ///   reciprocal is not finite.
+    /// * Returns an error when `min <= 0.0` for `CurveKind::Log2`.
+    /// * Returns an error when the unit is `Unit::Decibels` or `Unit::Percent`
+    ///   for `CurveKind::Log2`.
+    /// * Returns an error when the `neg_infinity_clamp` of `Unit::Decibels` is
+    ///   above `min`.
+    /// * Returns an error when the kind of curve is not supported.
+    pub fn build(&self) -> Result<NormalMap, BuildError> {
+        // NaN is incomparable, so it is rejected as well.
+        if !matches!(
+            self.min.partial_cmp(&self.max),
+            Some(Ordering::Less | Ordering::Equal)
+        ) {
+            return Err(BuildError::MinAboveMax);
+        }
+
+        match (self.kind, self.exponent) {
+            (CurveKind::Power, None) => return Err(BuildError::MissingExponent),
+            (CurveKind::Power, Some(exponent)) => {
+                if exponent <= 0.0 || !exponent.is_finite() || !(1.0 / exponent).is_finite() {
+                    return Err(BuildError::InvalidExponent);
+                }
+            }
+            (_, Some(_)) => return Err(BuildError::UnexpectedExponent),
+            (_, None) => {}
+        }
+
+        if let Unit::Decibels {
+            neg_infinity_clamp: Some(clamp),
+        } = self.unit
+        {
+            if clamp > self.min {
+                return Err(BuildError::ClampAboveMin);
+            }
+        }
+
+        match self.kind {
+            CurveKind::Linear => Ok(NormalMap::linear(self.min, self.max, self.unit)),
+            CurveKind::Power => Ok(NormalMap::power(
+                self.min,
+                self.max,
+                self.exponent.unwrap_or(1.0),
+                self.unit,
+            )),
+            CurveKind::Log2 => {
+                // The unit is checked first, since a decibel range is usually
+                // negative as well.
+                let frequency = match self.unit {
+                    Unit::Generic => false,
+                    Unit::Frequency => true,
+                    Unit::Decibels { .. } => return Err(BuildError::DecibelsOnLog2),
+                    Unit::Percent => return Err(BuildError::UnsupportedUnit),
+                };
+
+                if self.min <= 0.0 {
+                    return Err(BuildError::NonPositiveLog2Range);
+                }
+
+                if frequency {
+                    Ok(NormalMap::log2_frequency(self.min, self.max))
+                } else {
+                    Ok(NormalMap::log2(self.min, self.max))
+                }
+            }
+            _ => Err(BuildError::UnsupportedKind),
+        }
+    }
+}
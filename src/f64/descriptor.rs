@@ -0,0 +1,410 @@
+//! A compact, human-editable string form for mappers.
+//!
+//! The grammar is a list of fields separated by `:`. Whitespace around
+//! each field is ignored, and keywords are case-insensitive.
+//!
+//! ```text
+//! descriptor = "lin"      ":" min ":" max [ ":" unit ]
+//!            | "pow"      ":" min ":" max ":" exponent [ ":" unit ]
+//!            | "powraw"   ":" min ":" max ":" exponent [ ":" unit ]
+//!            | "log2"     ":" min ":" max [ ":" "hz" [ ":" "oct" ] ]
+//!            | "discrete" ":" int ":" int [ ":" "rev" ] [ ":" ( "floor" | "ceil" ) ]
+//!            | "group"    ":" int ":" int { ":" int ":" int }
+//!            | "bilin"    ":" min ":" center_raw ":" center_norm ":" max
+//!            | "step"     ":" min ":" max ":" step
+//!            | "smooth"   ":" min ":" max [ ":" "quintic" ]
+//!            | "note"     ":" ref_a4_hz ":" int ":" int
+//!            | "wrap"     ":" min ":" max
+//!            | "piece"    ":" value ":" value { ":" value }
+//! unit       = "generic" | "hz" | "pct" | "db" [ ":" neg_infinity_clamp ]
+//! ```
+
+use std::convert::TryFrom;
+
+use super::{
+    BilinearMap, DiscreteMap, LinearMap, Log2Map, Mapper, NoteMap, PiecewiseMap, PowerMap,
+    RoundingMode, SmoothOrder, SmoothstepMap, SteppedMap, Unit, WrappingMap,
+};
+use crate::ParseError;
+
+pub fn to_descriptor(mapper: &Mapper) -> String {
+    match mapper {
+        Mapper::Lin(map) => format!("lin:{}:{}{}", map.min(), map.max(), unit_suffix(map.unit())),
+        Mapper::Pow(map) => format!(
+            "{}:{}:{}:{}{}",
+            if map.is_raw_exponent() {
+                "powraw"
+            } else {
+                "pow"
+            },
+            map.min(),
+            map.max(),
+            map.exponent(),
+            unit_suffix(map.unit())
+        ),
+        Mapper::Log2(map) => format!(
+            "log2:{}:{}{}{}",
+            map.min(),
+            map.max(),
+            unit_suffix(map.unit()),
+            if map.is_octave_snapped() { ":oct" } else { "" }
+        ),
+        Mapper::Discrete(map) if !map.groups().is_empty() => {
+            let mut descriptor = String::from("group");
+            for (start, end) in map.groups().iter() {
+                descriptor.push_str(&format!(":{}:{}", start, end));
+            }
+            descriptor
+        }
+        Mapper::Discrete(map) => format!(
+            "discrete:{}:{}{}{}",
+            map.min(),
+            map.max(),
+            if map.is_reversed() { ":rev" } else { "" },
+            match map.rounding_mode() {
+                RoundingMode::Nearest => "",
+                RoundingMode::Floor => ":floor",
+                RoundingMode::Ceil => ":ceil",
+            }
+        ),
+        Mapper::Bilinear(map) => format!(
+            "bilin:{}:{}:{}:{}",
+            map.min(),
+            map.center_raw(),
+            map.center_norm(),
+            map.max()
+        ),
+        Mapper::Stepped(map) => format!("step:{}:{}:{}", map.min(), map.max(), map.step()),
+        Mapper::Smoothstep(map) => format!(
+            "smooth:{}:{}{}",
+            map.min(),
+            map.max(),
+            match map.order() {
+                SmoothOrder::Cubic => "",
+                SmoothOrder::Quintic => ":quintic",
+            }
+        ),
+        Mapper::Note(map) => format!(
+            "note:{}:{}:{}",
+            map.ref_a4_hz(),
+            map.min_note(),
+            map.max_note()
+        ),
+        Mapper::Wrapping(map) => format!("wrap:{}:{}", map.min(), map.max()),
+        Mapper::Piecewise(map) => {
+            let mut descriptor = String::from("piece");
+            for sample in map.samples().iter() {
+                descriptor.push_str(&format!(":{}", sample));
+            }
+            descriptor
+        }
+    }
+}
+
+pub fn from_descriptor(s: &str) -> Result<Mapper, ParseError> {
+    let fields: Vec<&str> = s.split(':').map(|field| field.trim()).collect();
+    let kind = fields[0].to_lowercase();
+    let args = &fields[1..];
+
+    match kind.as_str() {
+        "lin" => {
+            if args.len() < 2 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let min = parse_float(args[0])?;
+            let max = parse_float(args[1])?;
+            let unit = parse_unit(&args[2..])?;
+
+            if min > max {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            Ok(Mapper::Lin(LinearMap::new(min, max, unit)))
+        }
+        "pow" | "powraw" => {
+            if args.len() < 3 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let min = parse_float(args[0])?;
+            let max = parse_float(args[1])?;
+            let exponent = parse_float(args[2])?;
+            let unit = parse_unit(&args[3..])?;
+
+            if exponent <= 0.0
+                || !exponent.is_finite()
+                || !(1.0 / exponent).is_finite()
+                || min > max
+            {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            if kind == "powraw" {
+                if min < 0.0
+                    || max < 0.0
+                    || !max.powf(exponent).is_finite()
+                    || matches!(unit, Unit::Decibels { .. })
+                {
+                    return Err(ParseError::InvalidArguments);
+                }
+
+                return Ok(Mapper::Pow(PowerMap::new_raw_exponent(
+                    min, max, exponent, unit,
+                )));
+            }
+
+            Ok(Mapper::Pow(PowerMap::new(min, max, exponent, unit)))
+        }
+        "log2" => {
+            if args.len() < 2 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let min = parse_float(args[0])?;
+            let max = parse_float(args[1])?;
+
+            if args.len() == 4 && args[3].eq_ignore_ascii_case("oct") {
+                if min <= 0.0 || max <= min || parse_unit(&args[2..3])? != Unit::Frequency {
+                    return Err(ParseError::InvalidArguments);
+                }
+
+                let num_octaves = (max / min).log2().round();
+                let map = Log2Map::octave_snapped(min, num_octaves as usize);
+                if map.max() != max {
+                    return Err(ParseError::InvalidArguments);
+                }
+
+                return Ok(Mapper::Log2(map));
+            }
+
+            let unit = parse_unit(&args[2..])?;
+
+            if min <= 0.0 || max <= 0.0 || min > max {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            match unit {
+                Unit::Generic => Ok(Mapper::Log2(Log2Map::new(min, max))),
+                Unit::Frequency => Ok(Mapper::Log2(Log2Map::new_frequency(min, max))),
+                Unit::Percent | Unit::Decibels { .. } => Err(ParseError::InvalidArguments),
+            }
+        }
+        "discrete" => {
+            if args.len() < 2 || args.len() > 4 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let min = parse_int(args[0])?;
+            let max = parse_int(args[1])?;
+
+            if min > max {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            let mut flags = args[2..].iter().map(|flag| flag.to_lowercase()).peekable();
+
+            let mut map = if flags.peek().is_some_and(|flag| flag == "rev") {
+                flags.next();
+                DiscreteMap::new_reversed(min, max)
+            } else {
+                DiscreteMap::new(min, max)
+            };
+
+            match flags.next() {
+                None => {}
+                Some(flag) if flag == "nearest" => {}
+                Some(flag) if flag == "floor" => map.set_rounding_mode(RoundingMode::Floor),
+                Some(flag) if flag == "ceil" => map.set_rounding_mode(RoundingMode::Ceil),
+                Some(_) => return Err(ParseError::InvalidArguments),
+            }
+            if flags.next().is_some() {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            Ok(Mapper::Discrete(map))
+        }
+        "group" => {
+            if args.is_empty() || args.len() % 2 == 1 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let mut groups = Vec::with_capacity(args.len() / 2);
+            for pair in args.chunks(2) {
+                groups.push((parse_int(pair[0])?, parse_int(pair[1])?));
+            }
+
+            if groups.iter().any(|&(start, end)| start > end)
+                || groups.windows(2).any(|pair| pair[0].1 >= pair[1].0)
+            {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            Ok(Mapper::Discrete(DiscreteMap::grouped(&groups)))
+        }
+        "bilin" => {
+            if args.len() != 4 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let min = parse_float(args[0])?;
+            let center_raw = parse_float(args[1])?;
+            let center_norm = parse_float(args[2])?;
+            let max = parse_float(args[3])?;
+
+            if !(min < center_raw && center_raw < max && center_norm > 0.0 && center_norm < 1.0) {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            Ok(Mapper::Bilinear(BilinearMap::new(
+                min,
+                center_raw,
+                center_norm,
+                max,
+            )))
+        }
+        "step" => {
+            if args.len() != 3 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let min = parse_float(args[0])?;
+            let max = parse_float(args[1])?;
+            let step = parse_float(args[2])?;
+
+            if !(step > 0.0 && step.is_finite()) || min > max {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            Ok(Mapper::Stepped(SteppedMap::new(min, max, step)))
+        }
+        "smooth" => {
+            if args.len() != 2 && args.len() != 3 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let min = parse_float(args[0])?;
+            let max = parse_float(args[1])?;
+
+            let order = match args.get(2).map(|order| order.to_lowercase()) {
+                None => SmoothOrder::Cubic,
+                Some(order) if order == "cubic" => SmoothOrder::Cubic,
+                Some(order) if order == "quintic" => SmoothOrder::Quintic,
+                Some(_) => return Err(ParseError::InvalidArguments),
+            };
+
+            if min > max {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            Ok(Mapper::Smoothstep(SmoothstepMap::new_order(
+                min, max, order,
+            )))
+        }
+        "note" => {
+            if args.len() != 3 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let ref_a4_hz = parse_float(args[0])?;
+            let min_note = parse_note(args[1])?;
+            let max_note = parse_note(args[2])?;
+
+            if ref_a4_hz <= 0.0 || min_note > max_note {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            Ok(Mapper::Note(NoteMap::new(ref_a4_hz, min_note, max_note)))
+        }
+        "wrap" => {
+            if args.len() != 2 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let min = parse_float(args[0])?;
+            let max = parse_float(args[1])?;
+
+            if min > max {
+                return Err(ParseError::InvalidArguments);
+            }
+
+            Ok(Mapper::Wrapping(WrappingMap::new(min, max)))
+        }
+        "piece" => {
+            if args.len() < 2 {
+                return Err(ParseError::WrongFieldCount);
+            }
+            let mut samples = Vec::with_capacity(args.len());
+            for arg in args.iter() {
+                samples.push(parse_float(arg)?);
+            }
+
+            match PiecewiseMap::from_samples(&samples) {
+                Ok(map) => Ok(Mapper::Piecewise(map)),
+                Err(_) => Err(ParseError::InvalidArguments),
+            }
+        }
+        _ => Err(ParseError::UnknownMapper(fields[0].to_string())),
+    }
+}
+
+/// Parse a unit using the same grammar as the unit of a descriptor, such
+/// as `"generic"`, `"hz"`, `"pct"`, `"db"`, or `"db:-90"`.
+///
+/// Whitespace around each field is ignored, and keywords are case-insensitive.
+impl TryFrom<&str> for Unit {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, ParseError> {
+        let fields: Vec<&str> = s.split(':').map(|field| field.trim()).collect();
+
+        parse_unit(&fields)
+    }
+}
+
+fn unit_suffix(unit: Unit) -> String {
+    match unit {
+        Unit::Generic => String::new(),
+        Unit::Frequency => String::from(":hz"),
+        Unit::Percent => String::from(":pct"),
+        Unit::Decibels {
+            neg_infinity_clamp: None,
+        } => String::from(":db"),
+        Unit::Decibels {
+            neg_infinity_clamp: Some(clamp_db),
+        } => format!(":db:{}", clamp_db),
+    }
+}
+
+fn parse_unit(fields: &[&str]) -> Result<Unit, ParseError> {
+    let kind = match fields.first() {
+        Some(kind) => kind.to_lowercase(),
+        None => return Ok(Unit::Generic),
+    };
+
+    match (kind.as_str(), fields.len()) {
+        ("generic", 1) => Ok(Unit::Generic),
+        ("hz", 1) => Ok(Unit::Frequency),
+        ("pct", 1) => Ok(Unit::Percent),
+        ("db", 1) => Ok(Unit::Decibels {
+            neg_infinity_clamp: None,
+        }),
+        ("db", 2) => Ok(Unit::Decibels {
+            neg_infinity_clamp: Some(parse_float(fields[1])?),
+        }),
+        ("generic", _) | ("hz", _) | ("pct", _) | ("db", _) => Err(ParseError::WrongFieldCount),
+        _ => Err(ParseError::UnknownUnit(fields[0].to_string())),
+    }
+}
+
+/// Parse a float field, rejecting NaN and infinity so that they can't reach
+/// the range checks of the constructors.
+fn parse_float(field: &str) -> Result<f64, ParseError> {
+    field
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| ParseError::InvalidNumber(field.to_string()))
+}
+
+fn parse_int(field: &str) -> Result<isize, ParseError> {
+    field
+        .parse::<isize>()
+        .map_err(|_| ParseError::InvalidNumber(field.to_string()))
+}
+
+fn parse_note(field: &str) -> Result<i32, ParseError> {
+    field
+        .parse::<i32>()
+        .map_err(|_| ParseError::InvalidNumber(field.to_string()))
+}
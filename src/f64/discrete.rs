@@ -1,16 +1,43 @@
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 
 use super::linear_base;
+use super::{clamp_normalized, clamp_value};
+use super::{LinearMap, Unit};
+
+/// How a `DiscreteMap` rounds a denormalized value to a discrete step
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to the nearest step, with halfway cases rounded away from zero
+    #[default]
+    Nearest,
+    /// Round down to the step at or below the value
+    Floor,
+    /// Round up to the step at or above the value
+    Ceil,
+}
 
 /// Discrete `isize` integer mapping
 ///
 /// A supplied enum may be used as well as long
 /// as it implements `From<isize> + Into<isize> + Copy + Clone`.
-#[derive(Debug)]
+///
+/// Two maps are equal (and hash the same) when they were constructed with the
+/// same `isize` bounds, direction, groups, and rounding mode.
+#[derive(Debug, Clone)]
 pub struct DiscreteMap {
+    min_int: isize,
+    max_int: isize,
     min: f64,
     max: f64,
+    reversed: bool,
+    start: f64,
+    end: f64,
+    min_normal: f64,
+    max_normal: f64,
     lin_base: linear_base::Generic,
+    groups: Vec<(isize, isize)>,
+    rounding_mode: RoundingMode,
 }
 
 impl DiscreteMap {
@@ -23,19 +50,255 @@ impl DiscreteMap {
     ///
     /// * min - the minimum of the range
     /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
     pub fn new<T>(min: T, max: T) -> Self
     where
         T: Into<isize> + Copy + Clone,
     {
-        let min: isize = min.into();
-        let max: isize = max.into();
+        let min_int: isize = min.into();
+        let max_int: isize = max.into();
+        assert!(min_int <= max_int);
 
-        let min = min as f64;
-        let max = max as f64;
+        let min = min_int as f64;
+        let max = max_int as f64;
 
         let lin_base = linear_base::Generic::new(min, max);
 
-        Self { min, max, lin_base }
+        Self {
+            min_int,
+            max_int,
+            min,
+            max,
+            reversed: false,
+            start: min,
+            end: max,
+            min_normal: 0.0,
+            max_normal: 1.0,
+            lin_base,
+            groups: Vec::new(),
+            rounding_mode: RoundingMode::Nearest,
+        }
+    }
+
+    /// Create a new `DiscreteMap` with a discrete `isize` integer range, where
+    /// `min` maps to `1.0` and `max` maps to `0.0`.
+    ///
+    /// A supplied enum may be used as well as long
+    /// as it implements `From<isize> + Into<isize> + Copy + Clone`.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    pub fn new_reversed<T>(min: T, max: T) -> Self
+    where
+        T: Into<isize> + Copy + Clone,
+    {
+        let map = Self::new(min, max);
+
+        Self {
+            reversed: true,
+            start: map.max,
+            end: map.min,
+            min_normal: 1.0,
+            max_normal: 0.0,
+            lin_base: linear_base::Generic::new(map.max, map.min),
+            ..map
+        }
+    }
+
+    /// Create a new `DiscreteMap` where each inclusive `(start, end)` range of
+    /// `isize` values behaves as one step.
+    ///
+    /// The groups are evenly spaced in the normalized range. Normalizing any
+    /// value in a group returns the normalized position of that group, and
+    /// denormalizing returns the `start` of the group. Values in a gap between
+    /// two groups snap to the nearest group.
+    ///
+    /// This is useful when several consecutive values should be treated as
+    /// one, such as firmware revisions binned by compatibility.
+    ///
+    /// # Arguments
+    ///
+    /// * groups - the inclusive `(start, end)` ranges, in ascending order
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `groups` is empty.
+    /// * Panics when `start > end` for any group.
+    /// * Panics when the groups overlap or are not in ascending order.
+    pub fn grouped(groups: &[(isize, isize)]) -> Self {
+        assert!(!groups.is_empty());
+        assert!(groups.iter().all(|&(start, end)| start <= end));
+        assert!(groups.windows(2).all(|pair| pair[0].1 < pair[1].0));
+
+        Self {
+            groups: groups.to_vec(),
+            ..Self::new(groups[0].0, groups[groups.len() - 1].1)
+        }
+    }
+
+    /// Change the range in place, keeping the same direction and rounding mode.
+    ///
+    /// Any groups set with [`DiscreteMap::grouped`] are removed.
+    ///
+    /// A supplied enum may be used as well as long
+    /// as it implements `From<isize> + Into<isize> + Copy + Clone`.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the new minimum of the range
+    /// * max - the new maximum of the range
+    pub fn set_range<T>(&mut self, min: T, max: T)
+    where
+        T: Into<isize> + Copy + Clone,
+    {
+        let rounding_mode = self.rounding_mode;
+
+        *self = if self.reversed {
+            Self::new_reversed(min, max)
+        } else {
+            Self::new(min, max)
+        };
+        self.rounding_mode = rounding_mode;
+    }
+
+    /// Set how a denormalized value is rounded to a discrete step. The
+    /// default is `RoundingMode::Nearest`.
+    ///
+    /// For example, `RoundingMode::Floor` makes sure that a control such as
+    /// a buffer size never exceeds the requested value.
+    ///
+    /// The rounding mode has no effect on a map created with
+    /// [`DiscreteMap::grouped`], which always snaps to the nearest group.
+    pub fn set_rounding_mode(&mut self, rounding_mode: RoundingMode) {
+        self.rounding_mode = rounding_mode;
+    }
+
+    /// How a denormalized value is rounded to a discrete step.
+    pub fn rounding_mode(&self) -> RoundingMode {
+        self.rounding_mode
+    }
+
+    /// The minimum of the range.
+    pub fn min(&self) -> isize {
+        self.min_int
+    }
+
+    /// The maximum of the range.
+    pub fn max(&self) -> isize {
+        self.max_int
+    }
+
+    /// The width of the range, `max - min`.
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// Returns `true` if `min` maps to `1.0` and `max` maps to `0.0`.
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// The groups set with [`DiscreteMap::grouped`], or an empty slice if
+    /// every value is its own step.
+    pub fn groups(&self) -> &[(isize, isize)] {
+        &self.groups
+    }
+
+    /// The index of the group nearest to an `f64` value.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn group_index(&self, value: f64) -> usize {
+        for (i, &(start, end)) in self.groups.iter().enumerate() {
+            if value <= end as f64 {
+                if i > 0 && value - (self.groups[i - 1].1 as f64) < start as f64 - value {
+                    return i - 1;
+                }
+                return i;
+            }
+        }
+
+        self.groups.len() - 1
+    }
+
+    /// The normalized position of the group with the given index.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn group_normal(&self, index: usize) -> f64 {
+        if self.groups.len() < 2 {
+            return 0.0;
+        }
+
+        index as f64 / (self.groups.len() - 1) as f64
+    }
+
+    /// The start of the group nearest to a normalized value.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn group_start(&self, normalized: f64) -> isize {
+        let last = (self.groups.len() - 1) as f64;
+        let index = (normalized.clamp(0.0, 1.0) * last).round() as usize;
+
+        self.groups[index].0
+    }
+
+    /// Round a denormalized `f64` value to a discrete step using the rounding mode.
+    ///
+    /// Values within float error of a step are treated as being on that step,
+    /// so that `Floor` and `Ceil` still round-trip with `normalize`.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn round_value(&self, value: f64) -> f64 {
+        let tolerance = (self.max - self.min).abs() * 4.0 * f64::EPSILON;
+
+        match self.rounding_mode {
+            RoundingMode::Nearest => value.round(),
+            RoundingMode::Floor => (value + tolerance).floor(),
+            RoundingMode::Ceil => (value - tolerance).ceil(),
+        }
+    }
+
+    /// Map a normalized bound of a forward map, `0.0` or `1.0`, to the
+    /// corresponding bound of this map, which may be reversed.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn orient_bound(&self, bound: f64) -> f64 {
+        if bound == 0.0 {
+            self.min_normal
+        } else {
+            self.max_normal
+        }
+    }
+
+    /// Returns `true` if the range has zero width, meaning every value
+    /// maps to the same normalized value.
+    pub fn is_degenerate(&self) -> bool {
+        self.max - self.min == 0.0
+    }
+
+    /// Clamp an `f64` value to the range of the map without normalizing it.
+    ///
+    /// The value is not rounded to the nearest step.
+    pub fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+
+    /// Create a continuous `LinearMap` over the same range with `Unit::Generic`.
+    ///
+    /// The `LinearMap` is never reversed.
+    ///
+    /// This is useful for smoothly interpolating between discrete
+    /// values, such as during a patch morph.
+    pub fn to_linear(&self) -> LinearMap {
+        LinearMap::new(self.min, self.max, Unit::Generic)
+    }
+
+    /// Format an `f64` value for display as the nearest discrete step.
+    pub fn format_value(&self, value: f64) -> String {
+        format!("{}", value.round() as isize)
     }
 
     /// Map a discrete `isize` value to the normalized range `[0.0, 1.0]`.
@@ -49,7 +312,7 @@ impl DiscreteMap {
         self.normalize_generic(value)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn normalize_generic<T>(&self, value: T) -> f64
     where
         T: Into<isize> + Copy + Clone,
@@ -57,33 +320,58 @@ impl DiscreteMap {
         let value: isize = value.into();
         let value = value as f64;
 
-        if value <= self.min {
-            return 0.0;
-        };
-        if value >= self.max {
-            return 1.0;
-        };
+        if !self.groups.is_empty() {
+            return self.group_normal(self.group_index(value));
+        }
+
+        if let Some(normalized) = clamp_value(value, self.min, self.max) {
+            return self.orient_bound(normalized);
+        }
 
         self.lin_base.normalize(value)
     }
 
+    /// Map a discrete `isize` value to the normalized range `[0.0, 1.0]`, also
+    /// returning `true` if the value was outside of the range and was clamped.
+    ///
+    /// This is useful for flagging out-of-range values, such as when importing
+    /// settings from a file.
+    pub fn normalize_saturating(&self, value: isize) -> (f64, bool) {
+        let saturated = value < self.min() || value > self.max();
+
+        (self.normalize_generic(value), saturated)
+    }
+
     /// Map an `f64` value to the normalized range `[0.0, 1.0]`.
     pub fn normalize_float(&self, value: f64) -> f64 {
+        debug_assert!(value.is_finite());
         self.normalize_generic_float(value)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn normalize_generic_float(&self, value: f64) -> f64 {
-        if value <= self.min {
-            return 0.0;
-        };
-        if value >= self.max {
-            return 1.0;
-        };
+        if !self.groups.is_empty() {
+            return self.group_normal(self.group_index(value.round()));
+        }
+
+        if let Some(normalized) = clamp_value(value, self.min, self.max) {
+            return self.orient_bound(normalized);
+        }
 
         self.lin_base.normalize(value.round())
     }
 
+    /// Map an `f64` value to the normalized range `[0.0, 1.0]`, also returning
+    /// the residual `value - nearest_step`.
+    ///
+    /// This is useful for dithering, or for showing how close a value is
+    /// to a step boundary.
+    pub fn normalize_with_residual(&self, value: f64) -> (f64, f64) {
+        let nearest_step = value.round().clamp(self.min, self.max);
+
+        (self.normalize_generic_float(value), value - nearest_step)
+    }
+
     /// Map an array of discrete `isize` values to the normalized range `[0.0, 1.0]`.
     ///
     /// A supplied enum may be used as well as long
@@ -124,39 +412,158 @@ impl DiscreteMap {
     where
         T: From<isize> + Copy + Clone,
     {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
         self.denormalize_generic(normalized)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn denormalize_generic<T>(&self, normalized: f64) -> T
     where
         T: From<isize> + Copy + Clone,
     {
-        if normalized <= 0.0 {
-            return (self.min as isize).into();
+        if !self.groups.is_empty() {
+            return self.group_start(normalized).into();
         }
-        if normalized >= 1.0 {
-            return (self.max as isize).into();
+
+        if let Some(bound) = clamp_normalized(
+            normalized,
+            (self.start as isize).into(),
+            (self.end as isize).into(),
+        ) {
+            return bound;
         }
 
-        (self.lin_base.denormalize(normalized).round() as isize).into()
+        (self.round_value(self.lin_base.denormalize(normalized)) as isize).into()
     }
 
     /// Un-map a normalized value to the corresponding `f64` value.
     pub fn denormalize_float(&self, normalized: f64) -> f64 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
         self.denormalize_generic_float(normalized)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn denormalize_generic_float(&self, normalized: f64) -> f64 {
-        if normalized <= 0.0 {
-            return self.min;
+        if !self.groups.is_empty() {
+            return self.group_start(normalized) as f64;
         }
-        if normalized >= 1.0 {
-            return self.max;
+
+        if let Some(bound) = clamp_normalized(normalized, self.start, self.end) {
+            return bound;
         }
 
-        self.lin_base.denormalize(normalized).round()
+        self.round_value(self.lin_base.denormalize(normalized))
+    }
+
+    /// Un-map a normalized value to the corresponding discrete `isize` value
+    /// by computing the index of the nearest step.
+    ///
+    /// Unlike [`DiscreteMap::denormalize`], the value is reconstructed from the
+    /// step index with integer arithmetic, so float error does not accumulate
+    /// in the result. This is useful for round-tripping large ranges.
+    ///
+    /// A supplied enum may be used as well as long
+    /// as it implements `From<isize> + Into<isize> + Copy + Clone`.
+    pub fn denormalize_step_exact<T>(&self, normalized: f64) -> T
+    where
+        T: From<isize> + Copy + Clone,
+    {
+        if !self.groups.is_empty() {
+            return self.group_start(normalized).into();
+        }
+
+        let num_steps = self.max() - self.min();
+        let index = normalized.clamp(0.0, 1.0) * num_steps as f64;
+        let tolerance = num_steps as f64 * 4.0 * f64::EPSILON;
+
+        // The index counts down from `max` when reversed, so flooring the
+        // value means rounding the index up.
+        let index = match (self.rounding_mode, self.reversed) {
+            (RoundingMode::Nearest, _) => index.round(),
+            (RoundingMode::Floor, false) | (RoundingMode::Ceil, true) => {
+                (index + tolerance).floor()
+            }
+            (RoundingMode::Floor, true) | (RoundingMode::Ceil, false) => (index - tolerance).ceil(),
+        } as isize;
+
+        if self.reversed {
+            (self.max() - index).into()
+        } else {
+            (self.min() + index).into()
+        }
+    }
+
+    /// Un-map a normalized value to the two discrete steps on either side of
+    /// it, returning `(lower, upper, blend)`.
+    ///
+    /// `lower` is the step at or below the unrounded value, `upper` is the next
+    /// step above it (or `lower` at the top of the range), and `blend` in
+    /// `[0.0, 1.0)` is how far the value is from `lower` towards `upper`. For a
+    /// map created with [`DiscreteMap::grouped`], the steps are the `start` of
+    /// each group.
+    ///
+    /// This is useful for crossfading between steps when automating a discrete
+    /// parameter, instead of switching at the nearest step. It does not change
+    /// the snapping behavior of [`DiscreteMap::denormalize`].
+    pub fn denormalize_interpolated(&self, normalized: f64) -> (isize, isize, f64) {
+        let normalized = normalized.clamp(0.0, 1.0);
+
+        if !self.groups.is_empty() {
+            let last = self.groups.len() - 1;
+            let position = normalized * last as f64;
+            let lower = (position.floor() as usize).min(last);
+            let upper = (lower + 1).min(last);
+
+            let blend = if lower == upper {
+                0.0
+            } else {
+                position - lower as f64
+            };
+
+            return (self.groups[lower].0, self.groups[upper].0, blend);
+        }
+
+        let value = self
+            .lin_base
+            .denormalize(normalized)
+            .clamp(self.min, self.max);
+        let lower = value.floor();
+
+        if lower >= self.max {
+            return (self.max_int, self.max_int, 0.0);
+        }
+
+        (lower as isize, lower as isize + 1, value - lower)
+    }
+
+    /// Snap a normalized value to the normalized position of the nearest
+    /// discrete step.
+    ///
+    /// This is useful for snapping a dragged control to the nearest detent.
+    pub fn snap_normalized(&self, normalized: f64) -> f64 {
+        self.normalize_generic_float(self.denormalize_generic_float(normalized))
+    }
+
+    /// Returns `true` if a normalized value is within `tolerance` of the
+    /// normalized position of a discrete step.
+    ///
+    /// The step positions are computed from the step count, so they are exact
+    /// rather than the result of normalizing each step. This is useful for
+    /// showing a "snapped" indicator while dragging a control.
+    pub fn is_on_step(&self, normalized: f64, tolerance: f64) -> bool {
+        let num_steps = if self.groups.is_empty() {
+            (self.max_int - self.min_int) as f64
+        } else {
+            (self.groups.len() - 1) as f64
+        };
+
+        if num_steps == 0.0 {
+            return (normalized - self.min_normal).abs() <= tolerance;
+        }
+
+        let index = (normalized.clamp(0.0, 1.0) * num_steps).round();
+
+        (normalized - (index / num_steps)).abs() <= tolerance
     }
 
     /// Un-map an array of normalized values to the corresponding discrete `isize` value.
@@ -182,12 +589,44 @@ impl DiscreteMap {
     ///
     /// Values will be processed up to the length of the shortest array.
     pub fn denormalize_array_float(&self, in_normalized: &[f64], out_values: &mut [f64]) {
+        self.denormalize_array_float_with(in_normalized, out_values, |value| value);
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub(super) fn denormalize_array_float_with<F: Fn(f64) -> f64>(
+        &self,
+        in_normalized: &[f64],
+        out_values: &mut [f64],
+        post: F,
+    ) {
         let min_len = std::cmp::min(in_normalized.len(), out_values.len());
         let input = &in_normalized[..min_len];
         let output = &mut out_values[..min_len];
 
         for i in 0..min_len {
-            output[i] = self.denormalize_generic_float(input[i]);
+            output[i] = post(self.denormalize_generic_float(input[i]));
         }
     }
 }
+
+impl PartialEq for DiscreteMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.min_int == other.min_int
+            && self.max_int == other.max_int
+            && self.reversed == other.reversed
+            && self.groups == other.groups
+            && self.rounding_mode == other.rounding_mode
+    }
+}
+
+impl Eq for DiscreteMap {}
+
+impl Hash for DiscreteMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.min_int.hash(state);
+        self.max_int.hash(state);
+        self.reversed.hash(state);
+        self.groups.hash(state);
+        self.rounding_mode.hash(state);
+    }
+}
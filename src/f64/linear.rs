@@ -1,5 +1,6 @@
 use super::linear_base;
 use super::Unit;
+use super::{clamp_normalized, clamp_value};
 
 use std::fmt::Debug;
 
@@ -7,10 +8,12 @@ use std::fmt::Debug;
 ///
 /// Please note if you use `Unit::Decibels`, then the decibels
 /// will be linearly mapped, not the raw amplitude.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LinearMap {
     min: f64,
     max: f64,
+    min_value: f64,
+    max_value: f64,
     lin_base: linear_base::Base,
 }
 
@@ -25,55 +28,194 @@ impl LinearMap {
     /// * min - the minimum of the range
     /// * max - the maximum of the range
     /// * unit - the type of unit
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
     pub fn new(min: f64, max: f64, unit: Unit) -> Self {
+        assert!(min <= max);
+
+        let lin_base = linear_base::Base::new(min, max, unit);
+        let (min_value, max_value) = lin_base.value_bounds(min, max);
+
+        Self {
+            min,
+            max,
+            min_value,
+            max_value,
+            lin_base,
+        }
+    }
+
+    /// Create a new `LinearMap` for linear mapping with `Unit::Generic`.
+    ///
+    /// Unlike [`LinearMap::new`], this can be used in a `const` context,
+    /// such as when defining parameters in a `static` table. It does not check
+    /// that `min <= max`.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    pub const fn new_const(min: f64, max: f64) -> Self {
         Self {
             min,
             max,
-            lin_base: linear_base::Base::new(min, max, unit),
+            min_value: min,
+            max_value: max,
+            lin_base: linear_base::Base::GenericConst(linear_base::GenericConst::new(min, max)),
         }
     }
 
+    /// Create a new `LinearMap` with `Unit::Generic` where the normalized value
+    /// `0.0` maps to `intercept`, and the value increases by `slope` per
+    /// normalized unit.
+    ///
+    /// This is the same as `LinearMap::new(intercept, intercept + slope, Unit::Generic)`.
+    ///
+    /// # Arguments
+    ///
+    /// * intercept - the value at the normalized value `0.0`
+    /// * slope - the change in value per normalized unit, must be > 0.0
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `slope <= 0.0`, since the minimum of the range must
+    ///   not be greater than the maximum.
+    pub fn from_slope_intercept(intercept: f64, slope: f64) -> Self {
+        assert!(slope > 0.0);
+
+        Self::new(intercept, intercept + slope, Unit::Generic)
+    }
+
+    /// Change the range in place, keeping the same unit.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the new minimum of the range
+    /// * max - the new maximum of the range
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        *self = Self::new(min, max, self.unit());
+    }
+
+    /// Shift the decibel range by `offset_db`, such as when changing the 0 dB
+    /// reference. The `neg_infinity_clamp` is shifted as well.
+    ///
+    /// This does nothing if the unit is not `Unit::Decibels`.
+    pub fn shift_db(&mut self, offset_db: f64) {
+        let unit = match self.unit() {
+            Unit::Decibels { neg_infinity_clamp } => Unit::Decibels {
+                neg_infinity_clamp: neg_infinity_clamp.map(|clamp_db| clamp_db + offset_db),
+            },
+            _ => return,
+        };
+
+        *self = Self::new(self.min + offset_db, self.max + offset_db, unit);
+    }
+
+    /// The minimum of the range.
+    ///
+    /// For `Unit::Decibels`, this is in decibels.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// The maximum of the range.
+    ///
+    /// For `Unit::Decibels`, this is in decibels.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// The width of the range, `max - min`.
+    ///
+    /// For `Unit::Decibels`, this is in decibels.
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// The type of unit.
+    pub fn unit(&self) -> Unit {
+        self.lin_base.unit()
+    }
+
+    /// The `neg_infinity_clamp` in decibels, or `None` if the unit is not
+    /// `Unit::Decibels` with a `neg_infinity_clamp`.
+    pub fn clamp_db(&self) -> Option<f64> {
+        self.lin_base.clamp_db()
+    }
+
+    /// The raw amplitude at or below which values are clamped to silence, or
+    /// `None` if the unit is not `Unit::Decibels` with a `neg_infinity_clamp`.
+    ///
+    /// This is useful for drawing the clamp threshold on a meter.
+    pub fn clamp_coeff(&self) -> Option<f64> {
+        self.lin_base.clamp_coeff()
+    }
+
+    /// Returns `true` if the range has zero width, meaning every value
+    /// maps to the same normalized value.
+    pub fn is_degenerate(&self) -> bool {
+        self.max - self.min == 0.0
+    }
+
+    /// Clamp an `f64` value to the range of the map without normalizing it.
+    ///
+    /// For `Unit::Decibels`, the value is a raw amplitude and is clamped to
+    /// the amplitudes of the range.
+    pub fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min_value).min(self.max_value)
+    }
+
+    /// Format an `f64` value for display with the given number of decimal places.
+    ///
+    /// For `Unit::Decibels`, the raw amplitude is displayed in decibels, and
+    /// amplitudes at or below the `neg_infinity_clamp` are displayed as `-inf dB`.
+    /// For `Unit::Frequency`, values are displayed in Hz, or kHz at and above 1000 Hz.
+    /// For `Unit::Percent`, values are displayed with a `%` suffix.
+    pub fn format_value(&self, value: f64, decimals: usize) -> String {
+        self.lin_base.format_value(value, decimals)
+    }
+
     /// Map an `f64` value to the normalized range `[0.0, 1.0]`.
     pub fn normalize(&self, value: f64) -> f64 {
+        debug_assert!(value.is_finite());
+
         match &self.lin_base {
-            linear_base::Base::Generic(base) => self.normalize_generic(value, &base),
-            linear_base::Base::DB(base) => self.normalize_db(value, &base),
-            linear_base::Base::DBClamped(base) => self.normalize_db_clamped(value, &base),
+            linear_base::Base::Generic(base)
+            | linear_base::Base::Frequency(base)
+            | linear_base::Base::Percent(base) => self.normalize_generic(value, base),
+            linear_base::Base::GenericConst(base) => {
+                self.normalize_generic(value, &base.to_generic())
+            }
+            linear_base::Base::DB(base) => self.normalize_db(value, base),
+            linear_base::Base::DBClamped(base) => self.normalize_db_clamped(value, base),
         }
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn normalize_generic(&self, value: f64, lin_base: &linear_base::Generic) -> f64 {
-        if value <= self.min {
-            return 0.0;
-        };
-        if value >= self.max {
-            return 1.0;
-        };
+        if let Some(normalized) = clamp_value(value, self.min_value, self.max_value) {
+            return normalized;
+        }
 
         lin_base.normalize(value)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn normalize_db(&self, value: f64, lin_base: &linear_base::DB) -> f64 {
-        if value <= self.min {
-            return 0.0;
-        };
-        if value >= self.max {
-            return 1.0;
-        };
+        if let Some(normalized) = clamp_value(value, self.min_value, self.max_value) {
+            return normalized;
+        }
 
         lin_base.normalize(value)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn normalize_db_clamped(&self, value: f64, lin_base: &linear_base::DBClamped) -> f64 {
-        if value <= self.min {
-            return 0.0;
-        };
-        if value >= self.max {
-            return 1.0;
-        };
+        if let Some(normalized) = clamp_value(value, self.min_value, self.max_value) {
+            return normalized;
+        }
 
         lin_base.normalize(value)
     }
@@ -87,64 +229,102 @@ impl LinearMap {
         let output = &mut out_normalized[..min_len];
 
         match &self.lin_base {
-            linear_base::Base::Generic(base) => {
+            linear_base::Base::Generic(base)
+            | linear_base::Base::Frequency(base)
+            | linear_base::Base::Percent(base) => {
+                for i in 0..min_len {
+                    output[i] = self.normalize_generic(input[i], base);
+                }
+            }
+            linear_base::Base::GenericConst(base) => {
+                let base = base.to_generic();
                 for i in 0..min_len {
                     output[i] = self.normalize_generic(input[i], &base);
                 }
             }
             linear_base::Base::DB(base) => {
                 for i in 0..min_len {
-                    output[i] = self.normalize_db(input[i], &base);
+                    output[i] = self.normalize_db(input[i], base);
                 }
             }
             linear_base::Base::DBClamped(base) => {
                 for i in 0..min_len {
-                    output[i] = self.normalize_db_clamped(input[i], &base);
+                    output[i] = self.normalize_db_clamped(input[i], base);
                 }
             }
         }
     }
 
+    /// Map a raw amplitude to the normalized range `[0.0, 1.0]`.
+    ///
+    /// This is the same as `normalize`, but states that the input is an
+    /// amplitude and not decibels.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when the unit is not `Unit::Decibels`.
+    pub fn amp_to_normalized(&self, amplitude: f64) -> f64 {
+        assert!(
+            matches!(self.unit(), Unit::Decibels { .. }),
+            "amp_to_normalized requires a map with `Unit::Decibels`"
+        );
+
+        self.normalize(amplitude)
+    }
+
+    /// Map a value in decibels to the normalized range `[0.0, 1.0]`, converting
+    /// it to a raw amplitude first.
+    ///
+    /// # Panics
+    ///
+    /// * Panics when the unit is not `Unit::Decibels`.
+    pub fn db_to_normalized(&self, db: f64) -> f64 {
+        assert!(
+            matches!(self.unit(), Unit::Decibels { .. }),
+            "db_to_normalized requires a map with `Unit::Decibels`"
+        );
+
+        self.normalize(linear_base::db_to_coeff(db))
+    }
+
     /// Un-map a normalized value to the corresponding `f64` value.
     pub fn denormalize(&self, normalized: f64) -> f64 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
+
         match &self.lin_base {
-            linear_base::Base::Generic(base) => self.denormalize_generic(normalized, &base),
-            linear_base::Base::DB(base) => self.denormalize_db(normalized, &base),
-            linear_base::Base::DBClamped(base) => self.denormalize_db_clamped(normalized, &base),
+            linear_base::Base::Generic(base)
+            | linear_base::Base::Frequency(base)
+            | linear_base::Base::Percent(base) => self.denormalize_generic(normalized, base),
+            linear_base::Base::GenericConst(base) => {
+                self.denormalize_generic(normalized, &base.to_generic())
+            }
+            linear_base::Base::DB(base) => self.denormalize_db(normalized, base),
+            linear_base::Base::DBClamped(base) => self.denormalize_db_clamped(normalized, base),
         }
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn denormalize_generic(&self, normalized: f64, lin_base: &linear_base::Generic) -> f64 {
-        if normalized <= 0.0 {
-            return self.min;
-        }
-        if normalized >= 1.0 {
-            return self.max;
+        if let Some(bound) = clamp_normalized(normalized, self.min_value, self.max_value) {
+            return bound;
         }
 
         lin_base.denormalize(normalized)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn denormalize_db(&self, normalized: f64, lin_base: &linear_base::DB) -> f64 {
-        if normalized <= 0.0 {
-            return self.min;
-        }
-        if normalized >= 1.0 {
-            return self.max;
+        if let Some(bound) = clamp_normalized(normalized, self.min_value, self.max_value) {
+            return bound;
         }
 
         lin_base.denormalize(normalized)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn denormalize_db_clamped(&self, normalized: f64, lin_base: &linear_base::DBClamped) -> f64 {
-        if normalized <= 0.0 {
-            return self.min;
-        }
-        if normalized >= 1.0 {
-            return self.max;
+        if let Some(bound) = clamp_normalized(normalized, self.min_value, self.max_value) {
+            return bound;
         }
 
         lin_base.denormalize(normalized)
@@ -154,24 +334,42 @@ impl LinearMap {
     ///
     /// Values will be processed up to the length of the shortest array.
     pub fn denormalize_array(&self, in_normalized: &[f64], out_values: &mut [f64]) {
+        self.denormalize_array_with(in_normalized, out_values, |value| value);
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub(super) fn denormalize_array_with<F: Fn(f64) -> f64>(
+        &self,
+        in_normalized: &[f64],
+        out_values: &mut [f64],
+        post: F,
+    ) {
         let min_len = std::cmp::min(in_normalized.len(), out_values.len());
         let input = &in_normalized[..min_len];
         let output = &mut out_values[..min_len];
 
         match &self.lin_base {
-            linear_base::Base::Generic(base) => {
+            linear_base::Base::Generic(base)
+            | linear_base::Base::Frequency(base)
+            | linear_base::Base::Percent(base) => {
+                for i in 0..min_len {
+                    output[i] = post(self.denormalize_generic(input[i], base));
+                }
+            }
+            linear_base::Base::GenericConst(base) => {
+                let base = base.to_generic();
                 for i in 0..min_len {
-                    output[i] = self.denormalize_generic(input[i], &base);
+                    output[i] = post(self.denormalize_generic(input[i], &base));
                 }
             }
             linear_base::Base::DB(base) => {
                 for i in 0..min_len {
-                    output[i] = self.denormalize_db(input[i], &base);
+                    output[i] = post(self.denormalize_db(input[i], base));
                 }
             }
             linear_base::Base::DBClamped(base) => {
                 for i in 0..min_len {
-                    output[i] = self.denormalize_db_clamped(input[i], &base);
+                    output[i] = post(self.denormalize_db_clamped(input[i], base));
                 }
             }
         }
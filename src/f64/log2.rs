@@ -1,8 +1,18 @@
 use std::fmt::Debug;
 
+use super::linear_base;
+use super::Unit;
+use super::{clamp_normalized, clamp_value};
+
 /// Logarithmic mapping using `log2`. This is useful for frequency (Hz) values.
-#[derive(Debug)]
+///
+/// The mapping is computed in the `log2` domain, so the relative error of a
+/// round trip stays close to the precision of `f64` across the whole range,
+/// even for very large ranges such as `1e-6` to `1e12`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Log2Map {
+    frequency: bool,
+    octave_snapped: bool,
     min: f64,
     max: f64,
     min_log2: f64,
@@ -22,9 +32,11 @@ impl Log2Map {
     /// # Panics
     ///
     /// * Panics when either `min` or `max` <= 0.0.
+    /// * Panics when `min > max`.
     pub fn new(min: f64, max: f64) -> Self {
         assert!(min > 0.0);
         assert!(max > 0.0);
+        assert!(min <= max);
 
         let min_log2 = min.log2();
         let range_log2 = max.log2() - min_log2;
@@ -36,6 +48,8 @@ impl Log2Map {
         };
 
         Self {
+            frequency: false,
+            octave_snapped: false,
             min,
             max,
             min_log2,
@@ -44,19 +58,147 @@ impl Log2Map {
         }
     }
 
+    /// Create a new `Log2Map` for logarithmic mapping using `log2`, using
+    /// `Unit::Frequency` for formatting values.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range in Hz, must be > 0.0
+    /// * max - the maximum of the range in Hz, must be > 0.0
+    ///
+    /// # Panics
+    ///
+    /// * Panics when either `min` or `max` <= 0.0.
+    /// * Panics when `min > max`.
+    pub fn new_frequency(min: f64, max: f64) -> Self {
+        Self {
+            frequency: true,
+            ..Self::new(min, max)
+        }
+    }
+
+    /// Create a new `Log2Map` spanning `num_octaves` octaves above `min_hz`,
+    /// where denormalized values snap to the nearest `min_hz * 2^k`.
+    ///
+    /// This is useful for octave dividers and organ footage stops. Values are
+    /// normalized as usual, so each octave lies at `k / num_octaves`.
+    ///
+    /// This uses `Unit::Frequency` for formatting values.
+    ///
+    /// # Arguments
+    ///
+    /// * min_hz - the minimum of the range in Hz, must be > 0.0
+    /// * num_octaves - the number of octaves above `min_hz`
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min_hz <= 0.0`.
+    pub fn octave_snapped(min_hz: f64, num_octaves: usize) -> Self {
+        let max_hz = min_hz * (num_octaves as f64).exp2();
+
+        Self {
+            octave_snapped: true,
+            ..Self::new_frequency(min_hz, max_hz)
+        }
+    }
+
+    /// Change the range in place, keeping the same unit.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the new minimum of the range, must be > 0.0
+    /// * max - the new maximum of the range, must be > 0.0
+    ///
+    /// # Panics
+    ///
+    /// * Panics when either `min` or `max` <= 0.0.
+    /// * Panics when `min > max`.
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        *self = Self {
+            frequency: self.frequency,
+            octave_snapped: self.octave_snapped,
+            ..Self::new(min, max)
+        };
+    }
+
+    /// The minimum of the range.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// The maximum of the range.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// The width of the range, `max - min`.
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// The number of octaves the range spans, `log2(max / min)`.
+    pub fn octave_span(&self) -> f64 {
+        self.range_log2
+    }
+
+    /// The geometric center of the range, `sqrt(min * max)`.
+    ///
+    /// For a frequency control this is the perceptual center of the range,
+    /// and it is the same value as `denormalize(0.5)` (unless the map is octave
+    /// snapped). This is useful for labeling the center of a control.
+    pub fn geometric_center(&self) -> f64 {
+        (self.min_log2 + (0.5 * self.range_log2)).exp2()
+    }
+
+    /// Returns `true` if denormalized values snap to whole octaves above `min`.
+    pub fn is_octave_snapped(&self) -> bool {
+        self.octave_snapped
+    }
+
+    /// Returns `true` if the range has zero width, meaning every value
+    /// maps to the same normalized value.
+    pub fn is_degenerate(&self) -> bool {
+        self.range_log2 <= 0.0
+    }
+
+    /// Clamp an `f64` value to the range of the map without normalizing it.
+    pub fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+
+    /// The type of unit.
+    ///
+    /// This is either `Unit::Generic` or `Unit::Frequency`.
+    pub fn unit(&self) -> Unit {
+        if self.frequency {
+            Unit::Frequency
+        } else {
+            Unit::Generic
+        }
+    }
+
+    /// Format an `f64` value for display with the given number of decimal places.
+    ///
+    /// For `Unit::Frequency`, values are displayed in Hz, or kHz at and above 1000 Hz.
+    pub fn format_value(&self, value: f64, decimals: usize) -> String {
+        if self.frequency {
+            linear_base::format_frequency(value, decimals)
+        } else {
+            format!("{:.*}", decimals, value)
+        }
+    }
+
     /// Map an `f64` value to the normalized range `[0.0, 1.0]`.
     pub fn normalize(&self, value: f64) -> f64 {
+        debug_assert!(value.is_finite());
         self.normalize_generic(value)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn normalize_generic(&self, value: f64) -> f64 {
-        if value <= self.min {
-            return 0.0;
-        };
-        if value >= self.max {
-            return 1.0;
-        };
+        if let Some(normalized) = clamp_value(value, self.min, self.max) {
+            return normalized;
+        }
 
         (value.log2() - self.min_log2) * self.range_log2_inv
     }
@@ -64,43 +206,150 @@ impl Log2Map {
     /// Map an array of `f64` values to the normalized range `[0.0, 1.0]`.
     ///
     /// Values will be processed up to the length of the shortest array.
+    ///
+    /// With the `fast-math` feature enabled, this uses a vectorizable `log2`
+    /// approximation with an absolute error below `2e-5` in the `log2` domain.
+    /// For ranges spanning at least one octave, the normalized values are within
+    /// `1e-4` of the precise result.
     pub fn normalize_array(&self, in_values: &[f64], out_normalized: &mut [f64]) {
         let min_len = std::cmp::min(in_values.len(), out_normalized.len());
         let input = &in_values[..min_len];
         let output = &mut out_normalized[..min_len];
 
+        #[cfg(not(feature = "fast-math"))]
         for i in 0..min_len {
             output[i] = self.normalize_generic(input[i]);
         }
+
+        #[cfg(feature = "fast-math")]
+        for i in 0..min_len {
+            output[i] = self.normalize_fast(input[i]);
+        }
+    }
+
+    #[cfg(feature = "fast-math")]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn normalize_fast(&self, value: f64) -> f64 {
+        let normalized = (fast_log2(value.max(self.min)) - self.min_log2) * self.range_log2_inv;
+
+        clamp_value(value, self.min, self.max).unwrap_or(normalized.clamp(0.0, 1.0))
     }
 
     /// Un-map a normalized value to the corresponding `f64` value.
     pub fn denormalize(&self, normalized: f64) -> f64 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
         self.denormalize_generic(normalized)
     }
 
-    #[inline(always)]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
     fn denormalize_generic(&self, normalized: f64) -> f64 {
-        if normalized <= 0.0 {
-            return self.min;
+        if let Some(bound) = clamp_normalized(normalized, self.min, self.max) {
+            return bound;
         }
-        if normalized >= 1.0 {
-            return self.max;
+
+        if self.octave_snapped {
+            return self.min * (normalized * self.range_log2).round().exp2();
         }
 
-        2.0f64.powf((normalized * self.range_log2) + self.min_log2)
+        ((normalized * self.range_log2) + self.min_log2).exp2()
+    }
+
+    /// Un-map a normalized value to the corresponding offset in cents from
+    /// the geometric center of the range.
+    pub fn denormalize_to_cents(&self, normalized: f64) -> f64 {
+        (normalized.clamp(0.0, 1.0) - 0.5) * self.range_log2 * 1200.0
     }
 
     /// Un-map an array of normalized values to the corresponding `f64` value.
     ///
     /// Values will be processed up to the length of the shortest array.
+    ///
+    /// With the `fast-math` feature enabled, this uses a vectorizable `exp2`
+    /// approximation with a relative error below `1e-5`.
     pub fn denormalize_array(&self, in_normalized: &[f64], out_values: &mut [f64]) {
+        self.denormalize_array_with(in_normalized, out_values, |value| value);
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub(super) fn denormalize_array_with<F: Fn(f64) -> f64>(
+        &self,
+        in_normalized: &[f64],
+        out_values: &mut [f64],
+        post: F,
+    ) {
         let min_len = std::cmp::min(in_normalized.len(), out_values.len());
         let input = &in_normalized[..min_len];
         let output = &mut out_values[..min_len];
 
+        #[cfg(feature = "fast-math")]
+        if !self.octave_snapped {
+            for i in 0..min_len {
+                output[i] = post(self.denormalize_fast(input[i]));
+            }
+            return;
+        }
+
         for i in 0..min_len {
-            output[i] = self.denormalize_generic(input[i]);
+            output[i] = post(self.denormalize_generic(input[i]));
         }
     }
+
+    #[cfg(feature = "fast-math")]
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn denormalize_fast(&self, normalized: f64) -> f64 {
+        let value = fast_exp2((normalized.clamp(0.0, 1.0) * self.range_log2) + self.min_log2);
+
+        clamp_normalized(normalized, self.min, self.max)
+            .unwrap_or(value.max(self.min).min(self.max))
+    }
+}
+
+#[cfg(feature = "fast-math")]
+const MANTISSA_BITS: u32 = f64::MANTISSA_DIGITS - 1;
+#[cfg(feature = "fast-math")]
+const EXPONENT_BIAS: i32 = f64::MAX_EXP - 1;
+
+/// Approximate `log2` of a positive normal value.
+///
+/// The exponent is read from the bits directly, and `log2` of the mantissa
+/// is approximated with a polynomial that is exact at powers of two.
+#[cfg(feature = "fast-math")]
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn fast_log2(value: f64) -> f64 {
+    let bits = value.to_bits();
+    let exponent = (bits >> MANTISSA_BITS) as i32 - EXPONENT_BIAS;
+    let mantissa_mask = (1 << MANTISSA_BITS) - 1;
+    let m = f64::from_bits((bits & mantissa_mask) | 1.0f64.to_bits()) - 1.0;
+
+    let p = 0.04424374;
+    let p = p * m - 0.19126251;
+    let p = p * m + 0.41355505;
+    let p = p * m - 0.7083722;
+    let p = p * m + 1.4418359;
+    let p = p * m;
+
+    exponent as f64 + p
+}
+
+/// Approximate `exp2` of a value whose result is a normal number.
+///
+/// The integer part is written to the exponent bits directly, and `exp2` of
+/// the fractional part is approximated with a polynomial.
+#[cfg(feature = "fast-math")]
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn fast_exp2(value: f64) -> f64 {
+    // Truncating is much cheaper than `floor()` without SSE4.1. Rounding
+    // negative integers down by one is harmless since `f` is then `1.0`.
+    let floor = value as i32 - (value < 0.0) as i32;
+    let f = value - floor as f64;
+
+    let p = 0.013683983;
+    let p = p * f + 0.051717735;
+    let p = p * f + 0.24162132;
+    let p = p * f + 0.69296955;
+    let p = p * f + 1.0000036;
+
+    let exponent = (floor + EXPONENT_BIAS) as u64;
+
+    f64::from_bits((exponent << MANTISSA_BITS) as _) * p
 }
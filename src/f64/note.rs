@@ -0,0 +1,198 @@
+use std::fmt::Debug;
+
+use super::linear_base;
+use super::{clamp_normalized, clamp_value};
+
+/// Mapping over a range of MIDI notes, where values snap to the frequencies
+/// (in Hz) of the nearest 12-tone equal-tempered note.
+///
+/// The normalized range is spaced evenly in notes, so the control moves
+/// smoothly across the octaves like a `Log2Map`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteMap {
+    ref_a4_hz: f64,
+    min_note: i32,
+    max_note: i32,
+    min: f64,
+    max: f64,
+    lin_base: linear_base::Generic,
+}
+
+impl NoteMap {
+    /// Create a new `NoteMap` over a range of MIDI notes, where note `69` (A4)
+    /// has the frequency `ref_a4_hz`.
+    ///
+    /// # Arguments
+    ///
+    /// * ref_a4_hz - the frequency of A4 in Hz (usually `440.0`), must be > 0.0
+    /// * min_note - the lowest MIDI note of the range
+    /// * max_note - the highest MIDI note of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `ref_a4_hz <= 0.0`.
+    /// * Panics when `min_note > max_note`.
+    pub fn new(ref_a4_hz: f64, min_note: i32, max_note: i32) -> Self {
+        assert!(ref_a4_hz > 0.0);
+        assert!(min_note <= max_note);
+
+        Self {
+            ref_a4_hz,
+            min_note,
+            max_note,
+            min: note_to_hz(ref_a4_hz, min_note as f64),
+            max: note_to_hz(ref_a4_hz, max_note as f64),
+            lin_base: linear_base::Generic::new(min_note as f64, max_note as f64),
+        }
+    }
+
+    /// Change the range of notes in place, keeping the same reference frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * min_note - the new lowest MIDI note of the range
+    /// * max_note - the new highest MIDI note of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min_note > max_note`.
+    pub fn set_range(&mut self, min_note: i32, max_note: i32) {
+        *self = Self::new(self.ref_a4_hz, min_note, max_note);
+    }
+
+    /// The frequency of A4 in Hz.
+    pub fn ref_a4_hz(&self) -> f64 {
+        self.ref_a4_hz
+    }
+
+    /// The lowest MIDI note of the range.
+    pub fn min_note(&self) -> i32 {
+        self.min_note
+    }
+
+    /// The highest MIDI note of the range.
+    pub fn max_note(&self) -> i32 {
+        self.max_note
+    }
+
+    /// The frequency in Hz of the lowest note of the range.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// The frequency in Hz of the highest note of the range.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Returns `true` if the range has zero width, meaning every value
+    /// maps to the same normalized value.
+    pub fn is_degenerate(&self) -> bool {
+        self.min_note == self.max_note
+    }
+
+    /// The MIDI note (which may be outside of the range) nearest to a
+    /// frequency in Hz.
+    pub fn nearest_note(&self, hz: f64) -> i32 {
+        self.hz_to_note(hz).round() as i32
+    }
+
+    /// Clamp an `f64` value to the range of the map without normalizing it.
+    ///
+    /// The value is not snapped to the nearest note.
+    pub fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+
+    /// Format an `f64` value for display in Hz, or kHz at and above 1000 Hz.
+    pub fn format_value(&self, value: f64, decimals: usize) -> String {
+        linear_base::format_frequency(value, decimals)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn hz_to_note(&self, hz: f64) -> f64 {
+        69.0 + (12.0 * (hz / self.ref_a4_hz).log2())
+    }
+
+    /// Map an `f64` value to the normalized range `[0.0, 1.0]`.
+    pub fn normalize(&self, value: f64) -> f64 {
+        debug_assert!(value.is_finite());
+        self.normalize_generic(value)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn normalize_generic(&self, value: f64) -> f64 {
+        if let Some(normalized) = clamp_value(value, self.min, self.max) {
+            return normalized;
+        }
+
+        self.lin_base.normalize(self.hz_to_note(value).round())
+    }
+
+    /// Map an array of `f64` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[f64], out_normalized: &mut [f64]) {
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        let input = &in_values[..min_len];
+        let output = &mut out_normalized[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.normalize_generic(input[i]);
+        }
+    }
+
+    /// Un-map a normalized value to the frequency in Hz of the nearest note.
+    pub fn denormalize(&self, normalized: f64) -> f64 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
+        self.denormalize_generic(normalized)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn denormalize_generic(&self, normalized: f64) -> f64 {
+        if let Some(bound) = clamp_normalized(normalized, self.min, self.max) {
+            return bound;
+        }
+
+        note_to_hz(
+            self.ref_a4_hz,
+            self.lin_base.denormalize(normalized).round(),
+        )
+    }
+
+    /// Un-map a normalized value to the nearest MIDI note.
+    pub fn denormalize_to_midi_note(&self, normalized: f64) -> i32 {
+        self.lin_base
+            .denormalize(normalized.clamp(0.0, 1.0))
+            .round() as i32
+    }
+
+    /// Un-map an array of normalized values to the frequency in Hz of the
+    /// nearest note.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[f64], out_values: &mut [f64]) {
+        self.denormalize_array_with(in_normalized, out_values, |value| value);
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub(super) fn denormalize_array_with<F: Fn(f64) -> f64>(
+        &self,
+        in_normalized: &[f64],
+        out_values: &mut [f64],
+        post: F,
+    ) {
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        let input = &in_normalized[..min_len];
+        let output = &mut out_values[..min_len];
+
+        for i in 0..min_len {
+            output[i] = post(self.denormalize_generic(input[i]));
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn note_to_hz(ref_a4_hz: f64, note: f64) -> f64 {
+    ref_a4_hz * ((note - 69.0) / 12.0).exp2()
+}
@@ -0,0 +1,227 @@
+use std::fmt::Debug;
+
+use super::linear_base;
+use super::{clamp_normalized, clamp_value};
+
+/// The maximum number of Newton iterations used to invert the quintic curve.
+const MAX_NEWTON_ITERATIONS: usize = 8;
+
+/// The order of the curve used by a `SmoothstepMap`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmoothOrder {
+    /// The cubic `3t² - 2t³`, whose first derivative is zero at both ends (C1)
+    Cubic,
+    /// The quintic `6t⁵ - 15t⁴ + 10t³`, also known as smootherstep, whose
+    /// first and second derivatives are zero at both ends (C2)
+    Quintic,
+}
+
+/// S-shaped mapping using the smoothstep family of curves.
+///
+/// The curve is applied when normalizing, so values near either end of the
+/// range change the normalized value slowly, and values near the center change
+/// it quickly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmoothstepMap {
+    min: f64,
+    max: f64,
+    order: SmoothOrder,
+    lin_base: linear_base::Generic,
+}
+
+impl SmoothstepMap {
+    /// Create a new `SmoothstepMap` using the cubic curve.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    pub fn new(min: f64, max: f64) -> Self {
+        Self::new_order(min, max, SmoothOrder::Cubic)
+    }
+
+    /// Create a new `SmoothstepMap` using the curve of the given order.
+    ///
+    /// For `SmoothOrder::Quintic`, the curve has no closed-form inverse, so
+    /// denormalizing uses at most 8 Newton iterations, stopping once the
+    /// curve is within `f64::EPSILON` of the normalized value.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    /// * order - the order of the curve
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    pub fn new_order(min: f64, max: f64, order: SmoothOrder) -> Self {
+        assert!(min <= max);
+
+        Self {
+            min,
+            max,
+            order,
+            lin_base: linear_base::Generic::new(min, max),
+        }
+    }
+
+    /// Change the range in place, keeping the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the new minimum of the range
+    /// * max - the new maximum of the range
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        *self = Self::new_order(min, max, self.order);
+    }
+
+    /// The minimum of the range.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// The maximum of the range.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// The width of the range, `max - min`.
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// The order of the curve.
+    pub fn order(&self) -> SmoothOrder {
+        self.order
+    }
+
+    /// Returns `true` if the range has zero width, meaning every value
+    /// maps to the same normalized value.
+    pub fn is_degenerate(&self) -> bool {
+        self.max - self.min == 0.0
+    }
+
+    /// Clamp an `f64` value to the range of the map without normalizing it.
+    pub fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+
+    /// Format an `f64` value for display with the given number of decimal places.
+    pub fn format_value(&self, value: f64, decimals: usize) -> String {
+        format!("{:.*}", decimals, value)
+    }
+
+    /// Map an `f64` value to the normalized range `[0.0, 1.0]`.
+    pub fn normalize(&self, value: f64) -> f64 {
+        debug_assert!(value.is_finite());
+        self.normalize_generic(value)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn normalize_generic(&self, value: f64) -> f64 {
+        if let Some(normalized) = clamp_value(value, self.min, self.max) {
+            return normalized;
+        }
+
+        let t = self.lin_base.normalize(value);
+
+        match self.order {
+            SmoothOrder::Cubic => t * t * (3.0 - (2.0 * t)),
+            SmoothOrder::Quintic => quintic(t),
+        }
+    }
+
+    /// Map an array of `f64` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[f64], out_normalized: &mut [f64]) {
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        let input = &in_values[..min_len];
+        let output = &mut out_normalized[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.normalize_generic(input[i]);
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding `f64` value.
+    pub fn denormalize(&self, normalized: f64) -> f64 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
+        self.denormalize_generic(normalized)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn denormalize_generic(&self, normalized: f64) -> f64 {
+        if let Some(bound) = clamp_normalized(normalized, self.min, self.max) {
+            return bound;
+        }
+
+        let t = match self.order {
+            SmoothOrder::Cubic => cubic_inverse(normalized),
+            SmoothOrder::Quintic => quintic_inverse(normalized),
+        };
+
+        self.lin_base.denormalize(t)
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f64` value.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[f64], out_values: &mut [f64]) {
+        self.denormalize_array_with(in_normalized, out_values, |value| value);
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub(super) fn denormalize_array_with<F: Fn(f64) -> f64>(
+        &self,
+        in_normalized: &[f64],
+        out_values: &mut [f64],
+        post: F,
+    ) {
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        let input = &in_normalized[..min_len];
+        let output = &mut out_values[..min_len];
+
+        for i in 0..min_len {
+            output[i] = post(self.denormalize_generic(input[i]));
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn quintic(t: f64) -> f64 {
+    t * t * t * ((t * ((6.0 * t) - 15.0)) + 10.0)
+}
+
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn cubic_inverse(y: f64) -> f64 {
+    0.5 - ((1.0 - (2.0 * y)).asin() / 3.0).sin()
+}
+
+/// Invert the quintic with Newton's method, starting from the inverse of the
+/// cubic which is already a close approximation.
+#[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+fn quintic_inverse(y: f64) -> f64 {
+    let mut t = cubic_inverse(y);
+
+    for _ in 0..MAX_NEWTON_ITERATIONS {
+        let error = quintic(t) - y;
+        if error.abs() <= f64::EPSILON {
+            break;
+        }
+
+        let derivative = 30.0 * t * t * (t - 1.0) * (t - 1.0);
+        if derivative <= f64::EPSILON {
+            break;
+        }
+
+        t = (t - (error / derivative)).clamp(0.0, 1.0);
+    }
+
+    t
+}
@@ -0,0 +1,210 @@
+use std::fmt::Debug;
+
+use super::linear_base;
+use super::{clamp_normalized, clamp_value};
+
+/// Linear mapping where values snap to evenly spaced steps above `min`.
+///
+/// Unlike `DiscreteMap`, the steps do not have to be whole numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SteppedMap {
+    min: f64,
+    max: f64,
+    step: f64,
+    step_inv: f64,
+    max_index: f64,
+    snapped_max: f64,
+    lin_base: linear_base::Generic,
+}
+
+impl SteppedMap {
+    /// Create a new `SteppedMap` for linear mapping where values snap to
+    /// evenly spaced steps above `min`.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    /// * step - the distance between two steps, must be > 0.0
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `step <= 0.0` or `step` is not finite.
+    /// * Panics when `min > max`.
+    pub fn new(min: f64, max: f64, step: f64) -> Self {
+        assert!(step > 0.0 && step.is_finite());
+        assert!(min <= max);
+
+        let step_inv = 1.0 / step;
+
+        // Allow for rounding error when the range is a whole number of steps.
+        let num_steps = (max - min).max(0.0) * step_inv;
+        let max_index = if (num_steps - num_steps.round()).abs() < 0.001 {
+            num_steps.round()
+        } else {
+            num_steps.floor()
+        };
+
+        // `max` is not always on a step, so the top of the range snaps down to
+        // the last step below it.
+        let snapped_max = min + (max_index * step);
+
+        Self {
+            min,
+            max,
+            step,
+            step_inv,
+            max_index,
+            snapped_max,
+            lin_base: linear_base::Generic::new(min, max),
+        }
+    }
+
+    /// Create a new `SteppedMap` whose step is `10^-decimals`, so that values
+    /// always align to a display with `decimals` decimal places.
+    ///
+    /// `min` and `max` are rounded to `decimals` decimal places.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    /// * decimals - the number of decimal places
+    ///
+    /// # Panics
+    ///
+    /// * Panics when the number of steps in the range is not finite, or is too
+    ///   large for every step to be represented by an `f64`.
+    pub fn from_resolution(min: f64, max: f64, decimals: usize) -> Self {
+        let scale = 10.0f64.powi(decimals as i32);
+        let min = (min * scale).round() / scale;
+        let max = (max * scale).round() / scale;
+
+        let num_steps = ((max - min) * scale).abs();
+        assert!(num_steps.is_finite() && num_steps <= (1u64 << f64::MANTISSA_DIGITS) as f64);
+
+        Self::new(min, max, 1.0 / scale)
+    }
+
+    /// Change the range in place, keeping the same step.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the new minimum of the range
+    /// * max - the new maximum of the range
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        *self = Self::new(min, max, self.step);
+    }
+
+    /// The minimum of the range.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// The maximum of the range.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// The width of the range, `max - min`.
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// The distance between two steps.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Returns `true` if the range has zero width, meaning every value
+    /// maps to the same normalized value.
+    pub fn is_degenerate(&self) -> bool {
+        self.max - self.min == 0.0
+    }
+
+    /// Clamp an `f64` value to the range of the map without normalizing it.
+    ///
+    /// The value is not snapped to the nearest step.
+    pub fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+
+    /// Format an `f64` value for display with the given number of decimal places.
+    pub fn format_value(&self, value: f64, decimals: usize) -> String {
+        format!("{:.*}", decimals, value)
+    }
+
+    /// Snap an `f64` value to the nearest step within the range.
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn snap(&self, value: f64) -> f64 {
+        let index = ((value - self.min) * self.step_inv).round();
+
+        self.min + (index.clamp(0.0, self.max_index) * self.step)
+    }
+
+    /// Map an `f64` value to the normalized range `[0.0, 1.0]`.
+    pub fn normalize(&self, value: f64) -> f64 {
+        debug_assert!(value.is_finite());
+        self.normalize_generic(value)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn normalize_generic(&self, value: f64) -> f64 {
+        if let Some(normalized) = clamp_value(value, self.min, self.max) {
+            return normalized;
+        }
+
+        self.lin_base.normalize(self.snap(value))
+    }
+
+    /// Map an array of `f64` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[f64], out_normalized: &mut [f64]) {
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        let input = &in_values[..min_len];
+        let output = &mut out_normalized[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.normalize_generic(input[i]);
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding `f64` value.
+    pub fn denormalize(&self, normalized: f64) -> f64 {
+        debug_assert!((-0.001..=1.001).contains(&normalized));
+        self.denormalize_generic(normalized)
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    fn denormalize_generic(&self, normalized: f64) -> f64 {
+        if let Some(value) = clamp_normalized(normalized, self.min, self.snapped_max) {
+            return value;
+        }
+
+        self.snap(self.lin_base.denormalize(normalized))
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f64` value.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[f64], out_values: &mut [f64]) {
+        self.denormalize_array_with(in_normalized, out_values, |value| value);
+    }
+
+    #[cfg_attr(not(feature = "no-force-inline"), inline(always))]
+    pub(super) fn denormalize_array_with<F: Fn(f64) -> f64>(
+        &self,
+        in_normalized: &[f64],
+        out_values: &mut [f64],
+        post: F,
+    ) {
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        let input = &in_normalized[..min_len];
+        let output = &mut out_values[..min_len];
+
+        for i in 0..min_len {
+            output[i] = post(self.denormalize_generic(input[i]));
+        }
+    }
+}
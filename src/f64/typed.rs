@@ -0,0 +1,85 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use super::marker::UnitMarker;
+use super::NormalMap;
+
+/// A `NormalMap` whose type of unit is checked at compile time.
+///
+/// This is useful for large parameter tables where a decibel map should never
+/// be used in place of a generic map. Use [`TypedNormalMap::into_dynamic`] to
+/// go back to the runtime-checked `NormalMap`.
+///
+/// # Example
+///
+/// ```
+/// # use normal_map::f64::{marker, NormalMap, TypedNormalMap, Unit};
+/// let gain: TypedNormalMap<marker::Decibels> = TypedNormalMap::from_dynamic(
+///     NormalMap::linear(-90.0, 6.0, Unit::Decibels { neg_infinity_clamp: Some(-90.0) }),
+/// )
+/// .unwrap();
+///
+/// assert!(TypedNormalMap::<marker::Generic>::from_dynamic(gain.into_dynamic()).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedNormalMap<U: UnitMarker> {
+    map: NormalMap,
+    unit: PhantomData<U>,
+}
+
+impl<U: UnitMarker> TypedNormalMap<U> {
+    /// Wrap a `NormalMap`, or return `None` if its unit is not of the type
+    /// `U` represents.
+    ///
+    /// Mappers without a unit, such as `Mapper::Discrete`, use `Unit::Generic`.
+    pub fn from_dynamic(map: NormalMap) -> Option<Self> {
+        if !U::matches(map.describe().unit) {
+            return None;
+        }
+
+        Some(Self {
+            map,
+            unit: PhantomData,
+        })
+    }
+
+    /// Erase the unit marker, returning the runtime-checked `NormalMap`.
+    pub fn into_dynamic(self) -> NormalMap {
+        self.map
+    }
+
+    /// The wrapped `NormalMap`.
+    pub fn as_dynamic(&self) -> &NormalMap {
+        &self.map
+    }
+
+    /// Map an `f64` value to the normalized range `[0.0, 1.0]`.
+    pub fn normalize(&self, value: f64) -> f64 {
+        self.map.normalize(value)
+    }
+
+    /// Map an array of `f64` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[f64], out_normalized: &mut [f64]) {
+        self.map.normalize_array(in_values, out_normalized);
+    }
+
+    /// Un-map a normalized value to the corresponding `f64` value.
+    pub fn denormalize(&self, normalized: f64) -> f64 {
+        self.map.denormalize(normalized)
+    }
+
+    /// Un-map an array of normalized values to the corresponding `f64` value.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[f64], out_values: &mut [f64]) {
+        self.map.denormalize_array(in_normalized, out_values);
+    }
+}
+
+impl<U: UnitMarker> From<TypedNormalMap<U>> for NormalMap {
+    fn from(map: TypedNormalMap<U>) -> Self {
+        map.into_dynamic()
+    }
+}
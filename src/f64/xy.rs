@@ -0,0 +1,55 @@
+use std::fmt::Debug;
+
+use super::NormalMap;
+
+/// A pair of independent `NormalMap`s for the two axes of a 2D control,
+/// such as an XY pad backed by two parameters.
+///
+/// # Example
+///
+/// ```
+/// # use normal_map::f64::{NormalMap, Unit, XYMap};
+/// let pad = XYMap::new(
+///     NormalMap::log2(20.0, 20480.0),
+///     NormalMap::linear(0.0, 1.0, Unit::Generic),
+/// );
+///
+/// let (x, y) = pad.normalize_point((640.0, 0.25));
+///
+/// assert!((x - 0.5).abs() <= 0.0001);
+/// assert!((y - 0.25).abs() <= 0.0001);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct XYMap {
+    /// The map of the horizontal axis
+    pub x: NormalMap,
+    /// The map of the vertical axis
+    pub y: NormalMap,
+}
+
+impl XYMap {
+    /// Create a new `XYMap` from the maps of the two axes.
+    ///
+    /// # Arguments
+    ///
+    /// * x - the map of the horizontal axis
+    /// * y - the map of the vertical axis
+    pub fn new(x: NormalMap, y: NormalMap) -> Self {
+        Self { x, y }
+    }
+
+    /// Map an `(x, y)` point of `f64` values to the normalized range
+    /// `[0.0, 1.0]` on each axis.
+    pub fn normalize_point(&self, point: (f64, f64)) -> (f64, f64) {
+        (self.x.normalize(point.0), self.y.normalize(point.1))
+    }
+
+    /// Un-map a normalized `(x, y)` point to the corresponding `f64` values
+    /// on each axis.
+    pub fn denormalize_point(&self, normalized: (f64, f64)) -> (f64, f64) {
+        (
+            self.x.denormalize(normalized.0),
+            self.y.denormalize(normalized.1),
+        )
+    }
+}
@@ -0,0 +1,276 @@
+//! Normal mapping of fixed-point `I16F16` values, using only fixed-point math.
+//!
+//! This is intended for deterministic DSP on platforms where float behavior
+//! can't be relied on, so every result is bit-for-bit reproducible. The
+//! normalized range `[0.0, 1.0]` is an `I16F16` as well, which gives a
+//! normalized resolution of `1.0 / 65536.0`.
+//!
+//! Only the curves that can be computed exactly with fixed-point arithmetic
+//! are supported:
+//!
+//! * `LinearMap` - Linear mapping.
+//! * `DiscreteMap` - Discrete integer mapping.
+//!
+//! The power, logarithmic, and other curves need transcendental functions,
+//! so they are not supported. For those, map the value with an `f32` map and
+//! convert it with `I16F16::from_num`.
+//!
+//! Please note that the width of the range, `max - min`, must fit in an
+//! `I16F16`, which means it must be less than `32768.0`.
+
+use ::fixed::types::I16F16;
+
+/// The type of mapping to use
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mapper {
+    /// Linear mapping
+    Lin(LinearMap),
+    /// Discrete integer mapping
+    Discrete(DiscreteMap),
+}
+
+/// A mapper than maps a range of `I16F16` values to and from the normalized
+/// `I16F16` range `[0.0, 1.0]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalMap {
+    /// The current mapper in use
+    pub mapper: Mapper,
+}
+
+impl NormalMap {
+    /// Create a new `NormalMap` with linear mapping.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    /// * Panics when `max - min` overflows an `I16F16`.
+    pub fn linear(min: I16F16, max: I16F16) -> Self {
+        Self {
+            mapper: Mapper::Lin(LinearMap::new(min, max)),
+        }
+    }
+
+    /// Create a new `NormalMap` with discrete integer mapping.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    /// * Panics when `min` or `max` is out of the range of an `I16F16`, or
+    ///   when `max - min` overflows an `I16F16`.
+    pub fn discrete(min: i32, max: i32) -> Self {
+        Self {
+            mapper: Mapper::Discrete(DiscreteMap::new(min, max)),
+        }
+    }
+
+    /// Map an `I16F16` value to the normalized range `[0.0, 1.0]`.
+    pub fn normalize(&self, value: I16F16) -> I16F16 {
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.normalize(value),
+            Mapper::Discrete(mapper) => mapper.normalize(value),
+        }
+    }
+
+    /// Map an array of `I16F16` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[I16F16], out_normalized: &mut [I16F16]) {
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.normalize_array(in_values, out_normalized),
+            Mapper::Discrete(mapper) => mapper.normalize_array(in_values, out_normalized),
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding `I16F16` value.
+    pub fn denormalize(&self, normalized: I16F16) -> I16F16 {
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.denormalize(normalized),
+            Mapper::Discrete(mapper) => mapper.denormalize(normalized),
+        }
+    }
+
+    /// Un-map an array of normalized values to the corresponding `I16F16` value.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[I16F16], out_values: &mut [I16F16]) {
+        match &self.mapper {
+            Mapper::Lin(mapper) => mapper.denormalize_array(in_normalized, out_values),
+            Mapper::Discrete(mapper) => mapper.denormalize_array(in_normalized, out_values),
+        }
+    }
+}
+
+/// Linear mapping of `I16F16` values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearMap {
+    min: I16F16,
+    max: I16F16,
+    range: I16F16,
+}
+
+impl LinearMap {
+    /// Create a new `LinearMap` for linear mapping.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    /// * Panics when `max - min` overflows an `I16F16`.
+    pub fn new(min: I16F16, max: I16F16) -> Self {
+        assert!(min <= max);
+
+        let range = max
+            .checked_sub(min)
+            .expect("the width of the range overflows an I16F16");
+
+        Self { min, max, range }
+    }
+
+    /// The minimum of the range.
+    pub fn min(&self) -> I16F16 {
+        self.min
+    }
+
+    /// The maximum of the range.
+    pub fn max(&self) -> I16F16 {
+        self.max
+    }
+
+    /// Map an `I16F16` value to the normalized range `[0.0, 1.0]`.
+    ///
+    /// A range of zero width always maps to `0.0`.
+    pub fn normalize(&self, value: I16F16) -> I16F16 {
+        if self.range == I16F16::ZERO {
+            return I16F16::ZERO;
+        }
+
+        (value.clamp(self.min, self.max) - self.min) / self.range
+    }
+
+    /// Map an array of `I16F16` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[I16F16], out_normalized: &mut [I16F16]) {
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        let input = &in_values[..min_len];
+        let output = &mut out_normalized[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.normalize(input[i]);
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding `I16F16` value.
+    pub fn denormalize(&self, normalized: I16F16) -> I16F16 {
+        self.min + (normalized.clamp(I16F16::ZERO, I16F16::ONE) * self.range)
+    }
+
+    /// Un-map an array of normalized values to the corresponding `I16F16` value.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[I16F16], out_values: &mut [I16F16]) {
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        let input = &in_normalized[..min_len];
+        let output = &mut out_values[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.denormalize(input[i]);
+        }
+    }
+}
+
+/// Discrete integer mapping of `I16F16` values.
+///
+/// Values are rounded to the nearest integer, with ties rounded away from
+/// zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscreteMap {
+    lin_map: LinearMap,
+}
+
+impl DiscreteMap {
+    /// Create a new `DiscreteMap` for discrete integer mapping.
+    ///
+    /// # Arguments
+    ///
+    /// * min - the minimum of the range
+    /// * max - the maximum of the range
+    ///
+    /// # Panics
+    ///
+    /// * Panics when `min > max`.
+    /// * Panics when `min` or `max` is out of the range of an `I16F16`, or
+    ///   when `max - min` overflows an `I16F16`.
+    pub fn new(min: i32, max: i32) -> Self {
+        Self {
+            lin_map: LinearMap::new(I16F16::from_num(min), I16F16::from_num(max)),
+        }
+    }
+
+    /// The minimum of the range.
+    pub fn min(&self) -> i32 {
+        self.lin_map.min.to_num()
+    }
+
+    /// The maximum of the range.
+    pub fn max(&self) -> i32 {
+        self.lin_map.max.to_num()
+    }
+
+    /// Map an `I16F16` value to the normalized range `[0.0, 1.0]`, rounding it
+    /// to the nearest integer first.
+    pub fn normalize(&self, value: I16F16) -> I16F16 {
+        self.lin_map.normalize(value.saturating_round())
+    }
+
+    /// Map an array of `I16F16` values to the normalized range `[0.0, 1.0]`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn normalize_array(&self, in_values: &[I16F16], out_normalized: &mut [I16F16]) {
+        let min_len = std::cmp::min(in_values.len(), out_normalized.len());
+        let input = &in_values[..min_len];
+        let output = &mut out_normalized[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.normalize(input[i]);
+        }
+    }
+
+    /// Un-map a normalized value to the corresponding integer, as an `I16F16`.
+    pub fn denormalize(&self, normalized: I16F16) -> I16F16 {
+        self.lin_map.denormalize(normalized).round()
+    }
+
+    /// Un-map a normalized value to the corresponding integer.
+    pub fn denormalize_int(&self, normalized: I16F16) -> i32 {
+        self.denormalize(normalized).to_num()
+    }
+
+    /// Un-map an array of normalized values to the corresponding integer, as
+    /// an `I16F16`.
+    ///
+    /// Values will be processed up to the length of the shortest array.
+    pub fn denormalize_array(&self, in_normalized: &[I16F16], out_values: &mut [I16F16]) {
+        let min_len = std::cmp::min(in_normalized.len(), out_values.len());
+        let input = &in_normalized[..min_len];
+        let output = &mut out_values[..min_len];
+
+        for i in 0..min_len {
+            output[i] = self.denormalize(input[i]);
+        }
+    }
+}
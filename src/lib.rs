@@ -36,5 +36,17 @@
 #[cfg(test)]
 mod tests;
 
+mod cross_precision;
+mod error;
+
+#[cfg(feature = "half")]
+pub mod f16;
 pub mod f32;
 pub mod f64;
+#[cfg(feature = "fixed")]
+pub mod fixed;
+pub mod prelude;
+
+pub use error::{
+    BuildError, ClampAboveMin, LengthMismatch, NotMonotonic, NotNormalized, ParseError,
+};
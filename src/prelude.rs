@@ -0,0 +1,20 @@
+//! Re-exports of the commonly used types, using `f32` as the internal unit.
+//!
+//! ```
+//! use normal_map::prelude::*;
+//!
+//! let normal_map = NormalMap::linear(-50.0, 50.0, Unit::Generic);
+//!
+//! assert!((normal_map.normalize(25.0) - 0.75).abs() <= 0.0001);
+//! ```
+//!
+//! For `f64`, import `normal_map::f64::*` along with the error types instead.
+
+pub use crate::f32::{
+    BilinearMap, CurveDescriptor, CurveKind, DiscreteMap, LinearMap, Log2Map, Mapper, NormalMap,
+    NormalMapBuilder, Normalized, NoteMap, PiecewiseMap, PowerMap, RoundingMode, SmoothOrder,
+    SmoothstepMap, SteppedMap, TickSpacing, Unit, WrappingMap, XYMap,
+};
+pub use crate::{
+    BuildError, ClampAboveMin, LengthMismatch, NotMonotonic, NotNormalized, ParseError,
+};
@@ -42,6 +42,145 @@ fn linear_map_f64() {
     assert_approximate_f64(25.0, normal_map.denormalize(0.75));
 }
 
+#[test]
+fn linear_map_const() {
+    static MAPS_F32: [f32::NormalMap; 2] = [
+        f32::NormalMap::linear_const(-50.0, 50.0),
+        f32::NormalMap::linear_const(0.0, 0.0),
+    ];
+    static MAPS_F64: [f64::NormalMap; 1] = [f64::NormalMap::linear_const(-50.0, 50.0)];
+
+    assert_approximate_f32(0.75, MAPS_F32[0].normalize(25.0));
+    assert_approximate_f32(-25.0, MAPS_F32[0].denormalize(0.25));
+    assert_approximate_f32(0.0, MAPS_F32[1].normalize(0.0));
+
+    assert_approximate_f64(0.75, MAPS_F64[0].normalize(25.0));
+    assert_approximate_f64(-25.0, MAPS_F64[0].denormalize(0.25));
+}
+
+#[test]
+fn identity() {
+    static IDENTITY: f32::NormalMap = f32::NormalMap::IDENTITY;
+
+    for &n in [0.0, 0.25, 0.5, 1.0].iter() {
+        assert_eq!(n, IDENTITY.normalize(n));
+        assert_eq!(n, IDENTITY.denormalize(n));
+        assert_eq!(n as f64, f64::NormalMap::IDENTITY.denormalize(n as f64));
+    }
+
+    assert_eq!(
+        f32::NormalMap::linear(0.0, 1.0, f32::Unit::Generic),
+        f32::NormalMap::IDENTITY
+    );
+}
+
+#[test]
+fn linear_map_db_f32() {
+    let normal_map = f32::NormalMap::linear(
+        -90.0,
+        6.0,
+        f32::Unit::Decibels {
+            neg_infinity_clamp: Some(-90.0),
+        },
+    );
+
+    assert_approximate_f32(0.0, normal_map.normalize(0.0));
+    assert_approximate_f32(1.0, normal_map.normalize(2.0));
+    assert_approximate_f32(0.9375, normal_map.normalize(1.0));
+
+    assert_eq!(0.0, normal_map.denormalize(0.0));
+    assert_approximate_f32(1.0, normal_map.denormalize(0.9375));
+    assert_approximate_f32(1.9952623, normal_map.denormalize(1.0));
+
+    assert_eq!("-inf dB", normal_map.format_value(0.0, 1));
+    assert_eq!("-inf dB", normal_map.format_value(0.00001, 1));
+    assert_eq!("0.0 dB", normal_map.format_value(1.0, 1));
+    assert_eq!("-6.02 dB", normal_map.format_value(0.5, 2));
+}
+
+#[test]
+fn linear_map_db_f64() {
+    let normal_map = f64::NormalMap::linear(
+        -90.0,
+        6.0,
+        f64::Unit::Decibels {
+            neg_infinity_clamp: None,
+        },
+    );
+
+    assert_approximate_f64(0.0, normal_map.normalize(0.0));
+    assert_approximate_f64(1.0, normal_map.normalize(2.0));
+    assert_approximate_f64(0.9375, normal_map.normalize(1.0));
+
+    assert_approximate_f64(0.000031622776601683794, normal_map.denormalize(0.0));
+    assert_approximate_f64(1.0, normal_map.denormalize(0.9375));
+    assert_approximate_f64(1.9952623149688795, normal_map.denormalize(1.0));
+
+    assert_eq!("-inf dB", normal_map.format_value(0.0, 1));
+    assert_eq!(
+        "-90.0 dB",
+        normal_map.format_value(0.000031622776601683794, 1)
+    );
+}
+
+#[test]
+fn fader_db() {
+    let normal_map = f32::NormalMap::fader_db(6.0, -60.0);
+
+    assert_eq!(0.0, normal_map.denormalize(0.0));
+    assert_eq!(0.0, normal_map.normalize(0.0));
+    assert_eq!(0.0, normal_map.normalize(1e-9));
+    assert_approximate_f32(1.0, normal_map.normalize(2.0));
+    assert_approximate_f32(1.0, normal_map.denormalize(60.0 / 66.0));
+
+    let normal_map = f64::NormalMap::fader_db(0.0, -90.0);
+
+    assert_eq!(0.0, normal_map.denormalize(0.0));
+    assert_eq!(0.0, normal_map.normalize(1e-9));
+    assert_approximate_f64(1.0, normal_map.denormalize(1.0));
+}
+
+#[test]
+fn mixer_fader() {
+    let normal_map = f32::NormalMap::mixer_fader();
+
+    assert_approximate_f32(0.75, normal_map.normalize(1.0));
+    assert_approximate_f32(1.0, normal_map.denormalize(0.75));
+    assert_approximate_f32(10.0f32.powf(0.3), normal_map.denormalize(1.0));
+    assert_eq!(0.0, normal_map.denormalize(0.0));
+
+    let normal_map = f64::NormalMap::mixer_fader();
+    assert_approximate_f64(0.75, normal_map.normalize(1.0));
+    assert_approximate_f64(6.0, normal_map.describe().max);
+}
+
+#[test]
+fn linear_map_shift_db() {
+    let mut map = f32::LinearMap::new(
+        -90.0,
+        6.0,
+        f32::Unit::Decibels {
+            neg_infinity_clamp: Some(-90.0),
+        },
+    );
+    assert_approximate_f32(0.9375, map.normalize(1.0));
+
+    map.shift_db(6.0);
+    assert_approximate_f32(-84.0, map.min());
+    assert_approximate_f32(12.0, map.max());
+    assert_approximate_f32(0.875, map.normalize(1.0));
+    assert_eq!(
+        f32::Unit::Decibels {
+            neg_infinity_clamp: Some(-84.0)
+        },
+        map.unit()
+    );
+
+    let mut map = f64::LinearMap::new(-50.0, 50.0, f64::Unit::Generic);
+    map.shift_db(6.0);
+    assert_approximate_f64(0.5, map.normalize(0.0));
+}
+
 #[test]
 fn power_map_f32() {
     let normal_map = f32::NormalMap::power(-50.0, 50.0, 0.5, f32::Unit::Generic);
@@ -84,6 +223,161 @@ fn power_map_f64() {
     assert_approximate_f64(25.0, normal_map.denormalize(0.5625));
 }
 
+#[test]
+fn power_map_negative_range() {
+    let map_f32 = f32::NormalMap::power(-100.0, 100.0, 0.5, f32::Unit::Generic);
+    let map_f64 = f64::NormalMap::power(-100.0, 100.0, 0.5, f64::Unit::Generic);
+
+    assert_eq!(0.0, map_f32.normalize(-100.0));
+    assert_eq!(-100.0, map_f32.denormalize(0.0));
+    assert_eq!(100.0, map_f32.denormalize(1.0));
+    assert_approximate_f32(0.0625, map_f32.normalize(-50.0));
+    assert_approximate_f32(0.25, map_f32.normalize(0.0));
+    assert_approximate_f64(0.5625, map_f64.normalize(50.0));
+
+    for &value in [
+        -100.0, -99.0, -75.0, -50.0, -1.0, 0.0, 1.0, 33.0, 50.0, 100.0,
+    ]
+    .iter()
+    {
+        let normalized = map_f32.normalize(value);
+        assert!(normalized.is_finite());
+        assert!((map_f32.denormalize(normalized) - value).abs() < 1e-3);
+
+        let normalized = map_f64.normalize(value as f64);
+        assert!(normalized.is_finite());
+        assert!((map_f64.denormalize(normalized) - value as f64).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn power_map_raw_exponent() {
+    let map_f32 = f32::PowerMap::new_raw_exponent(0.0, 4.0, 2.0, f32::Unit::Generic);
+
+    assert!(map_f32.is_raw_exponent());
+    assert_approximate_f32(0.0, map_f32.normalize(-1.0));
+    assert_approximate_f32(1.0, map_f32.normalize(5.0));
+    assert_approximate_f32(0.0625, map_f32.normalize(1.0));
+    assert_approximate_f32(0.25, map_f32.normalize(2.0));
+    assert_approximate_f32(1.0, map_f32.denormalize(0.0625));
+    assert_approximate_f32(2.0, map_f32.denormalize(0.25));
+    assert_approximate_f32(4.0, map_f32.denormalize(1.0));
+
+    let map_f64 = f64::PowerMap::new_raw_exponent(0.0, 4.0, 2.0, f64::Unit::Generic);
+
+    assert_approximate_f64(0.25, map_f64.normalize(2.0));
+    assert_approximate_f64(2.0, map_f64.denormalize(0.25));
+
+    let mut map = f32::PowerMap::new_raw_exponent(0.0, 4.0, 2.0, f32::Unit::Generic);
+    map.set_range(0.0, 2.0);
+    assert!(map.is_raw_exponent());
+    assert_approximate_f32(0.25, map.normalize(1.0));
+}
+
+#[test]
+#[should_panic]
+fn linear_map_reversed_range() {
+    f32::NormalMap::linear(50.0, -50.0, f32::Unit::Generic);
+}
+
+#[test]
+#[should_panic]
+fn power_map_reversed_range() {
+    f64::NormalMap::power(1.0, 0.0, 2.0, f64::Unit::Generic);
+}
+
+#[test]
+#[should_panic]
+fn log2_map_reversed_range() {
+    f32::NormalMap::log2(20480.0, 20.0);
+}
+
+#[test]
+#[should_panic]
+fn discrete_map_reversed_range() {
+    f64::NormalMap::discrete::<isize>(5, -5);
+}
+
+#[test]
+fn reversed_range_descriptor() {
+    for descriptor in ["lin:50:-50", "pow:1:0:2", "log2:20480:20", "discrete:5:-5"].iter() {
+        assert_eq!(
+            Err(ParseError::InvalidArguments),
+            f32::NormalMap::from_descriptor(descriptor)
+        );
+    }
+}
+
+#[test]
+fn non_finite_descriptor() {
+    let descriptors = [
+        "lin:NaN:1",
+        "log2:1:NaN",
+        "pow:NaN:1:2",
+        "powraw:NaN:1:2",
+        "step:NaN:1:1",
+        "wrap:NaN:1",
+        "smooth:NaN:1",
+        "note:NaN:0:10",
+        "lin:0:inf",
+    ];
+
+    for descriptor in descriptors.iter() {
+        assert!(matches!(
+            f32::NormalMap::from_descriptor(descriptor),
+            Err(ParseError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            f64::NormalMap::from_descriptor(descriptor),
+            Err(ParseError::InvalidNumber(_))
+        ));
+    }
+}
+
+#[test]
+fn power_map_extreme_exponent() {
+    for exponent in [1e30, 1e-30].iter() {
+        let normal_map = f32::NormalMap::power(-50.0, 50.0, *exponent, f32::Unit::Generic);
+
+        for i in 0..=100 {
+            let normalized = i as f32 / 100.0;
+            let value = normal_map.denormalize(normalized);
+            assert!(value.is_finite());
+            assert!(normal_map.normalize(value).is_finite());
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn power_map_subnormal_exponent() {
+    f64::PowerMap::new(0.0, 1.0, 1e-320, f64::Unit::Generic);
+}
+
+#[test]
+#[should_panic(expected = "exponent must be positive and finite")]
+fn power_map_nan_exponent() {
+    f32::PowerMap::new(0.0, 1.0, (-1.0f32).sqrt(), f32::Unit::Generic);
+}
+
+#[test]
+#[should_panic(expected = "exponent must be positive and finite")]
+fn power_map_raw_exponent_nan() {
+    f64::PowerMap::new_raw_exponent(0.0, 1.0, (-1.0f64).sqrt(), f64::Unit::Generic);
+}
+
+#[test]
+#[should_panic]
+fn power_map_raw_exponent_overflow() {
+    f32::PowerMap::new_raw_exponent(0.0, 1000.0, 100.0, f32::Unit::Generic);
+}
+
+#[test]
+#[should_panic]
+fn power_map_raw_exponent_negative_range() {
+    f32::PowerMap::new_raw_exponent(-1.0, 4.0, 2.0, f32::Unit::Generic);
+}
+
 #[test]
 fn log_map_f32() {
     let normal_map = f32::NormalMap::log2(20.0, 20480.0);
@@ -126,6 +420,81 @@ fn log_map_f64() {
     assert_approximate_f64(3620.3867196751216, normal_map.denormalize(0.75));
 }
 
+#[test]
+fn log_map_octave_snapped() {
+    let map = f32::Log2Map::octave_snapped(55.0, 4);
+
+    assert!(map.is_octave_snapped());
+    assert_eq!(880.0, map.max());
+
+    let snap_points = [55.0, 110.0, 220.0, 440.0, 880.0];
+    for (k, &hz) in snap_points.iter().enumerate() {
+        let normalized = k as f32 / 4.0;
+        assert_eq!(hz, map.denormalize(normalized));
+        assert_approximate_f32(normalized, map.normalize(hz));
+    }
+
+    assert_eq!(110.0, map.denormalize(0.3));
+    assert_eq!(220.0, map.denormalize(0.4));
+
+    let mut out = [0.0; 2];
+    map.denormalize_array(&[0.2, 0.65], &mut out);
+    assert_eq!([110.0, 440.0], out);
+
+    let map = f64::Log2Map::octave_snapped(32.0, 4);
+    assert_eq!(64.0, map.denormalize(0.3));
+}
+
+#[test]
+fn frequency_format() {
+    let normal_map = f32::NormalMap::log2_frequency(20.0, 20480.0);
+
+    if let f32::Mapper::Log2(mapper) = &normal_map.mapper {
+        assert_eq!(f32::Unit::Frequency, mapper.unit());
+    }
+    assert_approximate_f32(0.1, normal_map.normalize(40.0));
+    assert_eq!("640.0 Hz", normal_map.format_value(640.0, 1));
+    assert_eq!("1.00 kHz", normal_map.format_value(1000.0, 2));
+    assert_eq!("20.5 kHz", normal_map.format_value(20480.0, 1));
+
+    let normal_map = f64::NormalMap::linear(20.0, 20000.0, f64::Unit::Frequency);
+
+    assert_approximate_f64(0.5, normal_map.normalize(10010.0));
+    assert_eq!("999 Hz", normal_map.format_value(999.0, 0));
+    assert_eq!("10.01 kHz", normal_map.format_value(10010.0, 2));
+}
+
+#[test]
+fn log_map_large_range() {
+    let map_f32 = f32::NormalMap::log2(1e-6, 1e12);
+    let map_f64 = f64::NormalMap::log2(1e-6, 1e12);
+
+    for i in 0..=18 {
+        let value = 10.0f64.powi(i - 6);
+
+        let round_trip = map_f32.denormalize(map_f32.normalize(value as f32)) as f64;
+        assert!(((round_trip - value) / value).abs() < 1e-5);
+
+        let round_trip = map_f64.denormalize(map_f64.normalize(value));
+        assert!(((round_trip - value) / value).abs() < 1e-13);
+    }
+}
+
+#[test]
+fn percent() {
+    let normal_map = f32::NormalMap::percent();
+
+    assert_approximate_f32(0.25, normal_map.normalize(25.0));
+    assert_approximate_f32(75.0, normal_map.denormalize(0.75));
+    assert_eq!("25%", normal_map.format_value(25.0, 0));
+    assert_eq!("12.5%", normal_map.format_value(12.5, 1));
+
+    let normal_map = f64::NormalMap::percent_normalized();
+
+    assert_approximate_f64(0.25, normal_map.normalize(0.25));
+    assert_approximate_f64(0.75, normal_map.denormalize(0.75));
+}
+
 #[test]
 fn discrete_map_f32() {
     let normal_map = f32::NormalMap::discrete::<isize>(-5, 5);
@@ -168,6 +537,2071 @@ fn discrete_map_f64() {
     assert_approximate_f64(3.0, normal_map.denormalize(0.8));
 }
 
+#[test]
+fn normalize_discrete_array() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Shape {
+        Sine,
+        Triangle,
+        Square,
+    }
+
+    impl From<Shape> for isize {
+        fn from(shape: Shape) -> isize {
+            shape as isize
+        }
+    }
+
+    let normal_map = f32::NormalMap::discrete(0isize, 2isize);
+    let mut out = [0.0; 3];
+    normal_map.normalize_discrete_array(&[Shape::Sine, Shape::Triangle, Shape::Square], &mut out);
+    assert_approximate_f32(0.0, out[0]);
+    assert_approximate_f32(0.5, out[1]);
+    assert_approximate_f32(1.0, out[2]);
+
+    let normal_map = f64::NormalMap::linear(0.0, 4.0, f64::Unit::Generic);
+    let mut out = [0.0; 2];
+    normal_map.normalize_discrete_array(&[Shape::Triangle, Shape::Square], &mut out);
+    assert_approximate_f64(0.25, out[0]);
+    assert_approximate_f64(0.5, out[1]);
+}
+
+#[test]
+fn discrete_map_grouped_f32() {
+    let discrete_map = f32::DiscreteMap::grouped(&[(0, 3), (4, 4), (8, 12)]);
+
+    assert_eq!(0, discrete_map.min());
+    assert_eq!(12, discrete_map.max());
+
+    assert_approximate_f32(0.0, discrete_map.normalize(0isize));
+    assert_approximate_f32(0.0, discrete_map.normalize(3isize));
+    assert_approximate_f32(0.5, discrete_map.normalize(4isize));
+    assert_approximate_f32(0.5, discrete_map.normalize(5isize));
+    assert_approximate_f32(1.0, discrete_map.normalize(7isize));
+    assert_approximate_f32(1.0, discrete_map.normalize(20isize));
+    assert_approximate_f32(0.0, discrete_map.normalize(-2isize));
+    assert_approximate_f32(0.0, discrete_map.normalize_float(2.6));
+
+    assert_eq!(0isize, discrete_map.denormalize::<isize>(0.2));
+    assert_eq!(4isize, discrete_map.denormalize::<isize>(0.5));
+    assert_eq!(8isize, discrete_map.denormalize::<isize>(1.0));
+    assert_approximate_f32(8.0, discrete_map.denormalize_float(0.8));
+}
+
+#[test]
+fn discrete_map_grouped_f64() {
+    let discrete_map = f64::DiscreteMap::grouped(&[(0, 3), (4, 4), (8, 12)]);
+
+    assert_eq!(0, discrete_map.min());
+    assert_eq!(12, discrete_map.max());
+
+    assert_approximate_f64(0.0, discrete_map.normalize(0isize));
+    assert_approximate_f64(0.0, discrete_map.normalize(3isize));
+    assert_approximate_f64(0.5, discrete_map.normalize(4isize));
+    assert_approximate_f64(0.5, discrete_map.normalize(5isize));
+    assert_approximate_f64(1.0, discrete_map.normalize(7isize));
+    assert_approximate_f64(1.0, discrete_map.normalize(20isize));
+    assert_approximate_f64(0.0, discrete_map.normalize(-2isize));
+    assert_approximate_f64(0.0, discrete_map.normalize_float(2.6));
+
+    assert_eq!(0isize, discrete_map.denormalize::<isize>(0.2));
+    assert_eq!(4isize, discrete_map.denormalize::<isize>(0.5));
+    assert_eq!(8isize, discrete_map.denormalize::<isize>(1.0));
+    assert_approximate_f64(8.0, discrete_map.denormalize_float(0.8));
+}
+
+#[test]
+#[should_panic]
+fn discrete_map_grouped_overlapping() {
+    f32::DiscreteMap::grouped(&[(0, 3), (3, 5)]);
+}
+
+#[test]
+fn discrete_map_hash_eq() {
+    use std::collections::HashMap;
+
+    let mut cache = HashMap::new();
+    cache.insert(f32::DiscreteMap::new(0isize, 4isize), "a");
+    cache.insert(f32::DiscreteMap::new_reversed(0isize, 4isize), "b");
+    cache.insert(f32::DiscreteMap::new(0isize, 4isize), "c");
+
+    assert_eq!(2, cache.len());
+    assert_eq!(
+        Some(&"c"),
+        cache.get(&f32::DiscreteMap::new(0isize, 4isize))
+    );
+
+    let large = isize::MAX - 1;
+    assert_eq!(large, f64::DiscreteMap::new(0isize, large).max());
+    assert_ne!(
+        f32::DiscreteMap::new(0isize, large),
+        f32::DiscreteMap::new(0isize, large - 1)
+    );
+}
+
+#[test]
+fn discrete_map_reversed() {
+    let normal_map = f32::NormalMap::discrete_reversed::<isize>(0, 4);
+
+    assert_approximate_f32(1.0, normal_map.normalize(0.0));
+    assert_approximate_f32(1.0, normal_map.normalize(-1.0));
+    assert_approximate_f32(0.0, normal_map.normalize(4.0));
+    assert_approximate_f32(0.0, normal_map.normalize(5.0));
+    assert_approximate_f32(0.75, normal_map.normalize(1.0));
+
+    assert_approximate_f32(4.0, normal_map.denormalize(0.0));
+    assert_approximate_f32(0.0, normal_map.denormalize(1.0));
+    assert_approximate_f32(3.0, normal_map.denormalize(0.3));
+
+    let map = f64::DiscreteMap::new_reversed::<isize>(0, 4);
+
+    assert_approximate_f64(1.0, map.normalize::<isize>(0));
+    assert_approximate_f64(0.25, map.normalize::<isize>(3));
+    assert_eq!(4, map.denormalize::<isize>(0.0));
+    assert_eq!(0, map.denormalize::<isize>(1.0));
+    assert_eq!(1, map.denormalize::<isize>(0.8));
+}
+
+#[test]
+fn discrete_denormalize_step_exact() {
+    let map_f32 = f32::DiscreteMap::new(1_000_000isize, 1_010_000isize);
+    let map_f64 = f64::DiscreteMap::new_reversed(-5000isize, 5000isize);
+
+    for i in 0..=10000isize {
+        let value = 1_000_000 + i;
+        let exact: isize = map_f32.denormalize_step_exact(map_f32.normalize(value));
+        assert_eq!(value, exact);
+
+        let value = -5000 + i;
+        let exact: isize = map_f64.denormalize_step_exact(map_f64.normalize(value));
+        assert_eq!(value, exact);
+    }
+}
+
+#[test]
+fn discrete_normalize_saturating() {
+    let map = f32::DiscreteMap::new(0isize, 4isize);
+
+    assert_eq!((0.5, false), map.normalize_saturating(2));
+    assert_eq!((0.0, false), map.normalize_saturating(0));
+    assert_eq!((1.0, true), map.normalize_saturating(100));
+
+    let map = f64::DiscreteMap::new_reversed(0isize, 4isize);
+    assert_eq!((1.0, true), map.normalize_saturating(-1));
+}
+
+#[test]
+fn discrete_snap_normalized() {
+    let map = f32::DiscreteMap::new::<isize>(0, 4);
+
+    assert_approximate_f32(0.0, map.snap_normalized(0.1));
+    assert_approximate_f32(0.25, map.snap_normalized(0.2));
+    assert_approximate_f32(0.75, map.snap_normalized(0.7));
+    assert_approximate_f32(1.0, map.snap_normalized(1.5));
+
+    let map = f64::DiscreteMap::new_reversed::<isize>(0, 4);
+
+    assert_approximate_f64(0.25, map.snap_normalized(0.2));
+    assert_approximate_f64(0.75, map.snap_normalized(0.7));
+}
+
+#[test]
+fn discrete_rounding_mode() {
+    let mut map = f32::DiscreteMap::new::<isize>(0, 10);
+    assert_eq!(f32::RoundingMode::Nearest, map.rounding_mode());
+    assert_eq!(4, map.denormalize::<isize>(0.44));
+    assert_eq!(5, map.denormalize::<isize>(0.46));
+
+    map.set_rounding_mode(f32::RoundingMode::Floor);
+    assert_eq!(4, map.denormalize::<isize>(0.44));
+    assert_eq!(4, map.denormalize::<isize>(0.46));
+    assert_approximate_f32(4.0, map.denormalize_float(0.46));
+
+    map.set_rounding_mode(f32::RoundingMode::Ceil);
+    assert_eq!(5, map.denormalize::<isize>(0.44));
+    assert_eq!(5, map.denormalize::<isize>(0.46));
+    assert_eq!(5, map.denormalize_step_exact::<isize>(0.44));
+
+    for i in 0..=10isize {
+        assert_eq!(i, map.denormalize::<isize>(map.normalize(i)));
+    }
+
+    map.set_range::<isize>(0, 20);
+    assert_eq!(f32::RoundingMode::Ceil, map.rounding_mode());
+
+    let mut map = f64::DiscreteMap::new_reversed::<isize>(0, 10);
+    map.set_rounding_mode(f64::RoundingMode::Floor);
+    assert_eq!(5, map.denormalize::<isize>(0.46));
+    assert_eq!(5, map.denormalize_step_exact::<isize>(0.46));
+}
+
+#[test]
+fn discrete_denormalize_interpolated() {
+    let map = f32::DiscreteMap::new::<isize>(0, 4);
+
+    let (lower, upper, blend) = map.denormalize_interpolated(0.6);
+    assert_eq!((2, 3), (lower, upper));
+    assert_approximate_f32(0.4, blend);
+
+    assert_eq!((0, 1, 0.0), map.denormalize_interpolated(0.0));
+    assert_eq!((4, 4, 0.0), map.denormalize_interpolated(1.0));
+
+    let map = f64::DiscreteMap::new_reversed::<isize>(0, 4);
+    let (lower, upper, blend) = map.denormalize_interpolated(0.6);
+    assert_eq!((1, 2), (lower, upper));
+    assert_approximate_f64(0.6, blend);
+
+    let map = f64::DiscreteMap::grouped(&[(0, 3), (4, 4), (8, 12)]);
+    let (lower, upper, blend) = map.denormalize_interpolated(0.75);
+    assert_eq!((4, 8), (lower, upper));
+    assert_approximate_f64(0.5, blend);
+}
+
+#[test]
+fn discrete_is_on_step() {
+    let map = f32::DiscreteMap::new::<isize>(0, 4);
+
+    assert!(map.is_on_step(0.0, 0.001));
+    assert!(map.is_on_step(0.75, 0.001));
+    assert!(map.is_on_step(0.7505, 0.001));
+    assert!(!map.is_on_step(0.7, 0.001));
+    assert!(map.is_on_step(0.5, 0.0));
+
+    let map = f64::DiscreteMap::grouped(&[(0, 3), (4, 4), (8, 12)]);
+
+    assert!(map.is_on_step(0.5, 0.0));
+    assert!(!map.is_on_step(0.25, 0.01));
+}
+
+#[test]
+fn discrete_normalize_with_residual() {
+    let map = f32::DiscreteMap::new::<isize>(-5, 5);
+
+    let (normalized, residual) = map.normalize_with_residual(2.75);
+    assert_approximate_f32(0.8, normalized);
+    assert_approximate_f32(-0.25, residual);
+
+    let (normalized, residual) = map.normalize_with_residual(-7.0);
+    assert_approximate_f32(0.0, normalized);
+    assert_approximate_f32(-2.0, residual);
+
+    let map = f64::DiscreteMap::new::<isize>(0, 10);
+
+    let (normalized, residual) = map.normalize_with_residual(3.25);
+    assert_approximate_f64(0.3, normalized);
+    assert_approximate_f64(0.25, residual);
+}
+
+#[test]
+fn discrete_to_linear() {
+    let linear_map = f32::DiscreteMap::new::<isize>(-5, 5).to_linear();
+
+    assert_approximate_f32(0.55, linear_map.normalize(0.5));
+    assert_approximate_f32(-2.5, linear_map.denormalize(0.25));
+
+    let linear_map = f64::DiscreteMap::new::<isize>(-5, 5).to_linear();
+
+    assert_approximate_f64(0.55, linear_map.normalize(0.5));
+    assert_approximate_f64(-2.5, linear_map.denormalize(0.25));
+}
+
+#[test]
+fn linear_map_from_slope_intercept() {
+    let linear_map = f32::LinearMap::from_slope_intercept(-10.0, 40.0);
+
+    assert_approximate_f32(-10.0, linear_map.min());
+    assert_approximate_f32(30.0, linear_map.max());
+    assert_approximate_f32(10.0, linear_map.denormalize(0.5));
+    assert_approximate_f32(0.25, linear_map.normalize(0.0));
+    assert_eq!(f32::Unit::Generic, linear_map.unit());
+
+    let linear_map = f64::LinearMap::from_slope_intercept(1.0, 0.5);
+    assert_approximate_f64(1.25, linear_map.denormalize(0.5));
+}
+
+#[test]
+#[should_panic]
+fn linear_map_from_slope_intercept_zero_slope() {
+    f32::LinearMap::from_slope_intercept(1.0, 0.0);
+}
+
+#[test]
+fn bilinear_map_f32() {
+    let normal_map = f32::NormalMap::bilinear(0.0, 1.0, 0.5, 10.0);
+
+    assert_approximate_f32(0.0, normal_map.normalize(0.0));
+    assert_approximate_f32(0.0, normal_map.normalize(-1.0));
+    assert_approximate_f32(1.0, normal_map.normalize(10.0));
+    assert_approximate_f32(1.0, normal_map.normalize(11.0));
+
+    assert_approximate_f32(0.0, normal_map.denormalize(0.0));
+    assert_approximate_f32(10.0, normal_map.denormalize(1.0));
+
+    assert_approximate_f32(0.5, normal_map.normalize(1.0));
+    assert_approximate_f32(0.25, normal_map.normalize(0.5));
+    assert_approximate_f32(0.75, normal_map.normalize(5.5));
+
+    assert_approximate_f32(1.0, normal_map.denormalize(0.5));
+    assert_approximate_f32(0.5, normal_map.denormalize(0.25));
+    assert_approximate_f32(5.5, normal_map.denormalize(0.75));
+}
+
+#[test]
+fn bilinear_map_f64() {
+    let normal_map = f64::NormalMap::bilinear(0.0, 1.0, 0.5, 10.0);
+
+    assert_approximate_f64(0.0, normal_map.normalize(0.0));
+    assert_approximate_f64(0.0, normal_map.normalize(-1.0));
+    assert_approximate_f64(1.0, normal_map.normalize(10.0));
+    assert_approximate_f64(1.0, normal_map.normalize(11.0));
+
+    assert_approximate_f64(0.0, normal_map.denormalize(0.0));
+    assert_approximate_f64(10.0, normal_map.denormalize(1.0));
+
+    assert_approximate_f64(0.5, normal_map.normalize(1.0));
+    assert_approximate_f64(0.25, normal_map.normalize(0.5));
+    assert_approximate_f64(0.75, normal_map.normalize(5.5));
+
+    assert_approximate_f64(1.0, normal_map.denormalize(0.5));
+    assert_approximate_f64(0.5, normal_map.denormalize(0.25));
+    assert_approximate_f64(5.5, normal_map.denormalize(0.75));
+}
+
+#[test]
+fn stepped_map_from_resolution_f32() {
+    let stepped_map = f32::SteppedMap::from_resolution(0.04, 100.0, 1);
+
+    assert_approximate_f32(0.0, stepped_map.min());
+    assert_approximate_f32(0.1, stepped_map.step());
+
+    assert_approximate_f32(0.123, stepped_map.normalize(12.34));
+    assert_approximate_f32(12.3, stepped_map.denormalize(0.1234));
+    assert_approximate_f32(50.0, stepped_map.denormalize(0.5));
+    assert_approximate_f32(100.0, stepped_map.denormalize(1.0));
+    assert_eq!(
+        "12.3",
+        stepped_map.format_value(stepped_map.denormalize(0.1234), 1)
+    );
+
+    let normal_map = f32::NormalMap::stepped(0.0, 1.0, 0.3);
+
+    assert_approximate_f32(0.6, normal_map.denormalize(0.55));
+    assert_approximate_f32(0.9, normal_map.denormalize(1.0));
+    assert_approximate_f32(1.0, normal_map.normalize(1.0));
+}
+
+#[test]
+fn stepped_map_from_resolution_f64() {
+    let stepped_map = f64::SteppedMap::from_resolution(0.04, 100.0, 1);
+
+    assert_approximate_f64(0.0, stepped_map.min());
+    assert_approximate_f64(0.1, stepped_map.step());
+
+    assert_approximate_f64(0.123, stepped_map.normalize(12.34));
+    assert_approximate_f64(12.3, stepped_map.denormalize(0.1234));
+    assert_approximate_f64(50.0, stepped_map.denormalize(0.5));
+    assert_approximate_f64(100.0, stepped_map.denormalize(1.0));
+    assert_eq!(
+        "12.3",
+        stepped_map.format_value(stepped_map.denormalize(0.1234), 1)
+    );
+
+    let normal_map = f64::NormalMap::stepped(0.0, 1.0, 0.3);
+
+    assert_approximate_f64(0.6, normal_map.denormalize(0.55));
+    assert_approximate_f64(0.9, normal_map.denormalize(1.0));
+    assert_approximate_f64(1.0, normal_map.normalize(1.0));
+}
+
+#[test]
+#[should_panic]
+fn stepped_map_from_resolution_too_fine() {
+    f32::SteppedMap::from_resolution(0.0, 1000.0, 6);
+}
+
+#[test]
+fn smoothstep_map_f32() {
+    let normal_map = f32::NormalMap::smoothstep(0.0, 10.0, f32::SmoothOrder::Cubic);
+
+    assert_approximate_f32(0.0, normal_map.normalize(0.0));
+    assert_approximate_f32(0.5, normal_map.normalize(5.0));
+    assert_approximate_f32(0.15625, normal_map.normalize(2.5));
+    assert_approximate_f32(1.0, normal_map.normalize(10.0));
+    assert_approximate_f32(2.5, normal_map.denormalize(0.15625));
+    assert_approximate_f32(10.0, normal_map.denormalize(1.0));
+
+    let normal_map = f32::NormalMap::smoothstep(0.0, 10.0, f32::SmoothOrder::Quintic);
+
+    assert_approximate_f32(0.5, normal_map.normalize(5.0));
+    assert_approximate_f32(0.103515625, normal_map.normalize(2.5));
+    assert_approximate_f32(2.5, normal_map.denormalize(0.103515625));
+    assert_approximate_f32(5.0, normal_map.denormalize(0.5));
+
+    for i in 1..20 {
+        let value = i as f32 * 0.5;
+        assert_approximate_f32(value, normal_map.denormalize(normal_map.normalize(value)));
+    }
+}
+
+#[test]
+fn smoothstep_map_f64() {
+    let normal_map = f64::NormalMap::smoothstep(0.0, 10.0, f64::SmoothOrder::Cubic);
+
+    assert_approximate_f64(0.0, normal_map.normalize(0.0));
+    assert_approximate_f64(0.5, normal_map.normalize(5.0));
+    assert_approximate_f64(0.15625, normal_map.normalize(2.5));
+    assert_approximate_f64(1.0, normal_map.normalize(10.0));
+    assert_approximate_f64(2.5, normal_map.denormalize(0.15625));
+    assert_approximate_f64(10.0, normal_map.denormalize(1.0));
+
+    let normal_map = f64::NormalMap::smoothstep(0.0, 10.0, f64::SmoothOrder::Quintic);
+
+    assert_approximate_f64(0.5, normal_map.normalize(5.0));
+    assert_approximate_f64(0.103515625, normal_map.normalize(2.5));
+    assert_approximate_f64(2.5, normal_map.denormalize(0.103515625));
+    assert_approximate_f64(5.0, normal_map.denormalize(0.5));
+
+    for i in 1..20 {
+        let value = i as f64 * 0.5;
+        assert_approximate_f64(value, normal_map.denormalize(normal_map.normalize(value)));
+    }
+}
+
+#[test]
+fn note_map_f32() {
+    let normal_map = f32::NormalMap::note(440.0, 57, 81);
+
+    assert_approximate_f32(220.0, normal_map.denormalize(0.0));
+    assert_approximate_f32(440.0, normal_map.denormalize(0.5));
+    assert_approximate_f32(880.0, normal_map.denormalize(1.0));
+    assert_approximate_f32(440.0 * (1.0f32 / 12.0).exp2(), normal_map.denormalize(0.54));
+
+    assert_approximate_f32(0.5, normal_map.normalize(440.0));
+    assert_approximate_f32(0.5, normal_map.normalize(450.0));
+    assert_approximate_f32(0.0, normal_map.normalize(100.0));
+
+    let note_map = f32::NoteMap::new(432.0, 0, 127);
+    assert_eq!(69, note_map.denormalize_to_midi_note(69.0 / 127.0));
+    assert_eq!(127, note_map.denormalize_to_midi_note(1.0));
+    assert_eq!(69, note_map.nearest_note(440.0));
+    assert_approximate_f32(432.0, note_map.denormalize(69.0 / 127.0));
+}
+
+#[test]
+fn note_map_f64() {
+    let normal_map = f64::NormalMap::note(440.0, 57, 81);
+
+    assert_approximate_f64(220.0, normal_map.denormalize(0.0));
+    assert_approximate_f64(440.0, normal_map.denormalize(0.5));
+    assert_approximate_f64(880.0, normal_map.denormalize(1.0));
+    assert_approximate_f64(440.0 * (1.0f64 / 12.0).exp2(), normal_map.denormalize(0.54));
+
+    assert_approximate_f64(0.5, normal_map.normalize(440.0));
+    assert_approximate_f64(0.5, normal_map.normalize(450.0));
+    assert_approximate_f64(0.0, normal_map.normalize(100.0));
+
+    let note_map = f64::NoteMap::new(432.0, 0, 127);
+    assert_eq!(69, note_map.denormalize_to_midi_note(69.0 / 127.0));
+    assert_eq!(127, note_map.denormalize_to_midi_note(1.0));
+    assert_eq!(69, note_map.nearest_note(440.0));
+    assert_approximate_f64(432.0, note_map.denormalize(69.0 / 127.0));
+}
+
+#[test]
+fn wrapping_map_f32() {
+    let normal_map = f32::NormalMap::wrapping(0.0, 360.0);
+
+    assert_approximate_f32(0.0, normal_map.normalize(0.0));
+    assert_approximate_f32(0.25, normal_map.normalize(90.0));
+    assert_approximate_f32(0.0, normal_map.normalize(360.0));
+
+    for x in [0.0, 45.0, 90.0, 270.0].iter() {
+        assert_approximate_f32(normal_map.normalize(*x), normal_map.normalize(360.0 + x));
+        assert_approximate_f32(normal_map.normalize(*x), normal_map.normalize(720.0 + x));
+        assert_approximate_f32(normal_map.normalize(*x), normal_map.normalize(x - 360.0));
+    }
+
+    assert_approximate_f32(0.0, normal_map.denormalize(0.0));
+    assert_approximate_f32(180.0, normal_map.denormalize(0.5));
+    assert_approximate_f32(normal_map.denormalize(0.0), normal_map.denormalize(1.0));
+
+    // Values just below `min` must not round up to a normalized `1.0`.
+    assert!((0.0..1.0).contains(&normal_map.normalize(-3.6e-7)));
+    assert_eq!(0.0, normal_map.denormalize(-1e-9));
+
+    assert_approximate_f32(30.0, normal_map.clamp(390.0));
+    assert_approximate_f32(330.0, normal_map.clamp(-30.0));
+}
+
+#[test]
+fn wrapping_map_f64() {
+    let normal_map = f64::NormalMap::wrapping(0.0, 360.0);
+
+    assert_approximate_f64(0.0, normal_map.normalize(0.0));
+    assert_approximate_f64(0.25, normal_map.normalize(90.0));
+    assert_approximate_f64(0.0, normal_map.normalize(360.0));
+
+    for x in [0.0, 45.0, 90.0, 270.0].iter() {
+        assert_approximate_f64(normal_map.normalize(*x), normal_map.normalize(360.0 + x));
+        assert_approximate_f64(normal_map.normalize(*x), normal_map.normalize(720.0 + x));
+        assert_approximate_f64(normal_map.normalize(*x), normal_map.normalize(x - 360.0));
+    }
+
+    assert_approximate_f64(0.0, normal_map.denormalize(0.0));
+    assert_approximate_f64(180.0, normal_map.denormalize(0.5));
+    assert_approximate_f64(normal_map.denormalize(0.0), normal_map.denormalize(1.0));
+
+    // Values just below `min` must not round up to a normalized `1.0`.
+    assert!((0.0..1.0).contains(&normal_map.normalize(-3.6e-15)));
+    assert_eq!(0.0, normal_map.denormalize(-1e-17));
+
+    assert_approximate_f64(30.0, normal_map.clamp(390.0));
+    assert_approximate_f64(330.0, normal_map.clamp(-30.0));
+}
+
+#[test]
+fn piecewise_map_f32() {
+    let normal_map = f32::NormalMap::piecewise(&[0.0, 1.0, 4.0, 9.0, 16.0]).unwrap();
+
+    assert_approximate_f32(0.0, normal_map.denormalize(0.0));
+    assert_approximate_f32(1.0, normal_map.denormalize(0.25));
+    assert_approximate_f32(2.5, normal_map.denormalize(0.375));
+    assert_approximate_f32(16.0, normal_map.denormalize(1.0));
+
+    assert_approximate_f32(0.375, normal_map.normalize(2.5));
+    assert_approximate_f32(0.75, normal_map.normalize(9.0));
+    assert_approximate_f32(1.0, normal_map.normalize(20.0));
+
+    assert_eq!(
+        Err(NotMonotonic { index: 2 }),
+        f32::PiecewiseMap::from_samples(&[0.0, 1.0, 1.0, 2.0])
+    );
+    assert!(f32::PiecewiseMap::from_samples(&[0.0, 2.0, 1.0]).is_err());
+
+    let mut map = f32::PiecewiseMap::from_samples(&[0.0, 1.0, 4.0]).unwrap();
+    map.set_range(10.0, 18.0);
+    assert_eq!(&[10.0, 12.0, 18.0], map.samples());
+}
+
+#[test]
+fn piecewise_map_f64() {
+    let normal_map = f64::NormalMap::piecewise(&[0.0, 1.0, 4.0, 9.0, 16.0]).unwrap();
+
+    assert_approximate_f64(0.0, normal_map.denormalize(0.0));
+    assert_approximate_f64(1.0, normal_map.denormalize(0.25));
+    assert_approximate_f64(2.5, normal_map.denormalize(0.375));
+    assert_approximate_f64(16.0, normal_map.denormalize(1.0));
+
+    assert_approximate_f64(0.375, normal_map.normalize(2.5));
+    assert_approximate_f64(0.75, normal_map.normalize(9.0));
+    assert_approximate_f64(1.0, normal_map.normalize(20.0));
+
+    assert_eq!(
+        Err(NotMonotonic { index: 2 }),
+        f64::PiecewiseMap::from_samples(&[0.0, 1.0, 1.0, 2.0])
+    );
+    assert!(f64::PiecewiseMap::from_samples(&[0.0, 2.0, 1.0]).is_err());
+
+    let mut map = f64::PiecewiseMap::from_samples(&[0.0, 1.0, 4.0]).unwrap();
+    map.set_range(10.0, 18.0);
+    assert_eq!(&[10.0, 12.0, 18.0], map.samples());
+}
+
+#[test]
+fn pitch_semitones_f32() {
+    let normal_map = f32::NormalMap::pitch_semitones(440.0, 12.0);
+
+    assert_approximate_f32(220.0, normal_map.denormalize(0.0));
+    assert_approximate_f32(440.0, normal_map.denormalize(0.5));
+    assert_approximate_f32(880.0, normal_map.denormalize(1.0));
+
+    assert_approximate_f32(-1200.0, normal_map.denormalize_to_cents(0.0).unwrap());
+    assert_approximate_f32(0.0, normal_map.denormalize_to_cents(0.5).unwrap());
+    assert_approximate_f32(600.0, normal_map.denormalize_to_cents(0.75).unwrap());
+    assert_approximate_f32(1200.0, normal_map.denormalize_to_cents(1.0).unwrap());
+
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    assert!(normal_map.denormalize_to_cents(0.5).is_none());
+}
+
+#[test]
+fn pitch_semitones_f64() {
+    let normal_map = f64::NormalMap::pitch_semitones(440.0, 12.0);
+
+    assert_approximate_f64(220.0, normal_map.denormalize(0.0));
+    assert_approximate_f64(440.0, normal_map.denormalize(0.5));
+    assert_approximate_f64(880.0, normal_map.denormalize(1.0));
+
+    assert_approximate_f64(-1200.0, normal_map.denormalize_to_cents(0.0).unwrap());
+    assert_approximate_f64(0.0, normal_map.denormalize_to_cents(0.5).unwrap());
+    assert_approximate_f64(600.0, normal_map.denormalize_to_cents(0.75).unwrap());
+    assert_approximate_f64(1200.0, normal_map.denormalize_to_cents(1.0).unwrap());
+}
+
+#[test]
+fn set_range_f32() {
+    let mut normal_map = f32::NormalMap::power(-50.0, 50.0, 0.5, f32::Unit::Generic);
+    normal_map.set_range(-25.0, 25.0);
+
+    assert_approximate_f32(0.25, normal_map.normalize(0.0));
+    assert_approximate_f32(25.0, normal_map.denormalize(1.0));
+
+    let mut normal_map = f32::NormalMap::log2_frequency(20.0, 20480.0);
+    normal_map.set_range(40.0, 10240.0);
+
+    assert_approximate_f32(0.5, normal_map.normalize(640.0));
+    assert_eq!("10.2 kHz", normal_map.format_value(10240.0, 1));
+
+    let mut normal_map = f32::NormalMap::discrete::<isize>(-5, 5);
+    normal_map.set_range(0.0, 4.0);
+
+    assert_approximate_f32(0.25, normal_map.normalize(1.0));
+}
+
+#[test]
+fn set_range_f64() {
+    let mut normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic);
+    normal_map.set_range(0.0, 10.0);
+
+    assert_approximate_f64(0.5, normal_map.normalize(5.0));
+    assert_approximate_f64(10.0, normal_map.denormalize(1.0));
+
+    let mut normal_map = f64::NormalMap::bilinear(0.0, 1.0, 0.5, 10.0);
+    normal_map.set_range(0.5, 1.5);
+
+    assert_approximate_f64(0.5, normal_map.normalize(1.0));
+    assert_approximate_f64(1.5, normal_map.denormalize(1.0));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic]
+fn debug_assert_normalized_range() {
+    f32::NormalMap::linear(0.0, 1.0, f32::Unit::Generic).denormalize(1.5);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic]
+fn debug_assert_finite_value() {
+    f64::NormalMap::log2(20.0, 20480.0).normalize(std::primitive::f64::NAN);
+}
+
+#[test]
+fn normalized_newtype() {
+    use std::convert::TryFrom;
+
+    assert_eq!(
+        Ok(0.25),
+        f32::Normalized::try_from(0.25).map(f32::Normalized::get)
+    );
+    assert_eq!(Err(NotNormalized), f32::Normalized::try_from(1.5));
+    assert_eq!(Err(NotNormalized), f64::Normalized::try_from(-0.1));
+    assert!(f64::Normalized::try_from((-1.0f64).sqrt()).is_err());
+
+    assert_eq!(f32::Normalized::ONE, f32::Normalized::new_clamped(25.0));
+    assert_eq!(f64::Normalized::ZERO, f64::Normalized::new_clamped(-25.0));
+
+    let normal_map = f32::NormalMap::log2(20.0, 20480.0);
+    let normalized = f32::Normalized::try_from(0.5).unwrap();
+    assert_eq!(
+        normal_map.denormalize(0.5),
+        normal_map.denormalize_checked(normalized)
+    );
+
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic).with_margin(0.25);
+    let normalized = f64::Normalized::new_clamped(1.0);
+    assert_approximate_f64(25.0, normal_map.denormalize_checked(normalized));
+    assert_eq!(1.0, f64::from(normalized));
+}
+
+#[test]
+fn unchecked_paths_ignore_range() {
+    // The array and interpolation paths don't assert on the range, with or
+    // without a margin, so these must not panic in debug builds.
+    for normal_map in [
+        f32::NormalMap::linear(0.0, 10.0, f32::Unit::Generic),
+        f32::NormalMap::linear(0.0, 10.0, f32::Unit::Generic).with_margin(0.1),
+    ]
+    .iter()
+    {
+        let mut out = [0.0; 2];
+        normal_map.denormalize_array(&[-0.5, 1.5], &mut out);
+        assert_eq!(
+            [normal_map.denormalize(0.0), normal_map.denormalize(1.0)],
+            out
+        );
+
+        assert_approximate_f32(
+            normal_map.denormalize(1.0),
+            normal_map.interpolate_values(2.0, 8.0, 4.0),
+        );
+    }
+}
+
+#[test]
+fn with_dead_zone() {
+    let normal_map = f32::NormalMap::linear(-1.0, 1.0, f32::Unit::Generic).with_dead_zone(0.2);
+
+    assert_approximate_f32(0.2, normal_map.dead_zone());
+    assert_eq!(0.0, normal_map.denormalize(0.45));
+    assert_eq!(0.0, normal_map.denormalize(0.5));
+    assert_eq!(0.0, normal_map.denormalize(0.55));
+    assert_approximate_f32(-1.0, normal_map.denormalize(0.0));
+    assert_approximate_f32(-0.375, normal_map.denormalize(0.25));
+    assert_approximate_f32(1.0, normal_map.denormalize(1.0));
+
+    assert_approximate_f32(0.5, normal_map.normalize(0.0));
+    assert_approximate_f32(0.25, normal_map.normalize(-0.375));
+    assert_approximate_f32(0.85, normal_map.normalize(0.625));
+
+    let mut out = [0.0; 3];
+    normal_map.denormalize_array(&[0.0, 0.48, 1.0], &mut out);
+    assert_eq!([-1.0, 0.0, 1.0], out);
+
+    let normal_map = f64::NormalMap::linear(0.0, 100.0, f64::Unit::Generic)
+        .with_dead_zone(0.5)
+        .with_margin(0.25);
+
+    assert_approximate_f64(0.5, normal_map.dead_zone());
+    assert_approximate_f64(25.0, normal_map.denormalize(0.0));
+    assert_approximate_f64(50.0, normal_map.denormalize(0.7));
+    assert_approximate_f64(75.0, normal_map.denormalize(1.0));
+    assert_approximate_f64(0.8125, normal_map.normalize(56.25));
+}
+
+#[test]
+#[should_panic]
+fn with_dead_zone_too_wide() {
+    f32::NormalMap::IDENTITY.with_dead_zone(1.0);
+}
+
+#[test]
+fn with_margin_f32() {
+    let normal_map = f32::NormalMap::linear(0.0, 1.0, f32::Unit::Generic).with_margin(0.01);
+
+    assert_approximate_f32(0.01, normal_map.denormalize(0.0));
+    assert_approximate_f32(0.01, normal_map.denormalize(-0.001));
+    assert_approximate_f32(0.99, normal_map.denormalize(1.0));
+    assert_approximate_f32(0.5, normal_map.denormalize(0.5));
+
+    assert_approximate_f32(0.0, normal_map.normalize(0.0));
+    assert_approximate_f32(0.0, normal_map.normalize(0.01));
+    assert_approximate_f32(0.5, normal_map.normalize(0.5));
+    assert_approximate_f32(1.0, normal_map.normalize(0.99));
+
+    let mut out = [0.0f32; 2];
+    normal_map.denormalize_array(&[0.0, 1.0], &mut out);
+    assert_approximate_f32(0.01, out[0]);
+    assert_approximate_f32(0.99, out[1]);
+
+    normal_map.normalize_array(&[0.255, 0.745], &mut out);
+    assert_approximate_f32(0.25, out[0]);
+    assert_approximate_f32(0.75, out[1]);
+}
+
+#[test]
+fn with_margin_f64() {
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic).with_margin(0.25);
+
+    assert_approximate_f64(-25.0, normal_map.denormalize(0.0));
+    assert_approximate_f64(25.0, normal_map.denormalize(1.0));
+    assert_approximate_f64(0.0, normal_map.normalize(-25.0));
+    assert_approximate_f64(1.0, normal_map.normalize(25.0));
+    assert_approximate_f64(0.75, normal_map.normalize(12.5));
+}
+
+#[test]
+fn degenerate_maps() {
+    assert!(!f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic).is_degenerate());
+    assert!(f32::NormalMap::linear(5.0, 5.0, f32::Unit::Generic).is_degenerate());
+    assert!(f32::NormalMap::power(5.0, 5.0, 2.0, f32::Unit::Generic).is_degenerate());
+    assert!(f32::NormalMap::log2(20.0, 20.0).is_degenerate());
+    assert!(f32::NormalMap::discrete::<isize>(3, 3).is_degenerate());
+    assert!(!f32::NormalMap::bilinear(0.0, 1.0, 0.5, 10.0).is_degenerate());
+
+    assert!(!f64::NormalMap::log2(20.0, 20480.0).is_degenerate());
+    assert!(f64::NormalMap::linear(5.0, 5.0, f64::Unit::Generic).is_degenerate());
+}
+
+#[test]
+fn clamp() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    assert_approximate_f32(-50.0, normal_map.clamp(-52.0));
+    assert_approximate_f32(25.0, normal_map.clamp(25.0));
+    assert_approximate_f32(50.0, normal_map.clamp(52.0));
+
+    let normal_map = f32::NormalMap::log2(20.0, 20480.0);
+    assert_approximate_f32(20.0, normal_map.clamp(0.0));
+
+    let normal_map = f64::NormalMap::linear(
+        -12.0,
+        0.0,
+        f64::Unit::Decibels {
+            neg_infinity_clamp: None,
+        },
+    );
+    assert_approximate_f64(1.0, normal_map.clamp(2.0));
+    assert_approximate_f64(0.5, normal_map.clamp(0.5));
+
+    let normal_map = f64::NormalMap::discrete(0isize, 4isize);
+    assert_approximate_f64(4.0, normal_map.clamp(4.5));
+}
+
+#[test]
+fn describe() {
+    let normal_map = f32::NormalMap::power(
+        -90.0,
+        6.0,
+        0.5,
+        f32::Unit::Decibels {
+            neg_infinity_clamp: Some(-90.0),
+        },
+    );
+    assert_eq!(
+        f32::CurveDescriptor {
+            kind: f32::CurveKind::Power,
+            min: -90.0,
+            max: 6.0,
+            exponent: Some(0.5),
+            unit: f32::Unit::Decibels {
+                neg_infinity_clamp: Some(-90.0),
+            },
+        },
+        normal_map.describe()
+    );
+
+    let normal_map = f64::NormalMap::discrete(-5isize, 5isize);
+    assert_eq!(
+        f64::CurveDescriptor {
+            kind: f64::CurveKind::Discrete,
+            min: -5.0,
+            max: 5.0,
+            exponent: None,
+            unit: f64::Unit::Generic,
+        },
+        normal_map.describe()
+    );
+}
+
+#[test]
+fn descriptor_round_trip() {
+    let descriptors = [
+        "lin:-50:50",
+        "lin:-90:6:db",
+        "lin:-90:6:db:-90",
+        "pow:0:1:2",
+        "pow:-90:6:0.5:db:-80",
+        "powraw:0:4:2",
+        "log2:20:20480",
+        "log2:20:20480:hz",
+        "log2:55:880:hz:oct",
+        "lin:20:20000:hz",
+        "lin:0:100:pct",
+        "discrete:-5:5",
+        "discrete:0:4:rev",
+        "discrete:0:4:floor",
+        "discrete:0:4:rev:ceil",
+        "group:0:3:4:4:8:12",
+        "bilin:0:1:0.5:10",
+        "step:0:10:0.5",
+        "smooth:0:10",
+        "smooth:-1:1:quintic",
+        "note:440:21:108",
+        "wrap:0:360",
+        "piece:0:1:4:9:16",
+    ];
+
+    for descriptor in descriptors.iter() {
+        let map_f32 = f32::NormalMap::from_descriptor(descriptor).unwrap();
+        assert_eq!(*descriptor, map_f32.to_descriptor());
+
+        let map_f64 = f64::NormalMap::from_descriptor(descriptor).unwrap();
+        assert_eq!(*descriptor, map_f64.to_descriptor());
+    }
+
+    let normal_map = f32::NormalMap::from_descriptor(" LIN : -50 : 50 : Generic ").unwrap();
+    assert_eq!("lin:-50:50", normal_map.to_descriptor());
+
+    assert_eq!(
+        Err(ParseError::UnknownMapper(String::from("exp"))),
+        f32::NormalMap::from_descriptor("exp:0:1").map(|_| ())
+    );
+    assert_eq!(
+        Err(ParseError::UnknownUnit(String::from("ms"))),
+        f32::NormalMap::from_descriptor("lin:0:1:ms").map(|_| ())
+    );
+    assert_eq!(
+        Err(ParseError::InvalidNumber(String::from("a"))),
+        f32::NormalMap::from_descriptor("lin:a:1").map(|_| ())
+    );
+    assert_eq!(
+        Err(ParseError::WrongFieldCount),
+        f32::NormalMap::from_descriptor("log2:20").map(|_| ())
+    );
+    assert_eq!(
+        Err(ParseError::InvalidArguments),
+        f32::NormalMap::from_descriptor("log2:0:20").map(|_| ())
+    );
+}
+
+#[test]
+fn unit_try_from_str() {
+    use std::convert::TryFrom;
+
+    assert_eq!(Ok(f32::Unit::Generic), f32::Unit::try_from("Generic"));
+    assert_eq!(Ok(f32::Unit::Frequency), f32::Unit::try_from("hz"));
+    assert_eq!(
+        Ok(f64::Unit::Decibels {
+            neg_infinity_clamp: None
+        }),
+        f64::Unit::try_from("DB")
+    );
+    assert_eq!(
+        Ok(f32::Unit::Decibels {
+            neg_infinity_clamp: Some(-90.0)
+        }),
+        f32::Unit::try_from("db: -90")
+    );
+    assert_eq!(
+        Err(ParseError::UnknownUnit(String::from("ms"))),
+        f32::Unit::try_from("ms")
+    );
+    assert_eq!(
+        Err(ParseError::WrongFieldCount),
+        f32::Unit::try_from("hz:1")
+    );
+}
+
+#[test]
+fn denormalize_array_affine() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+
+    let mut out = [0.0f32; 3];
+    normal_map.denormalize_array_affine(&[0.0, 0.5, 0.75], &mut out, 2.0, 1.0);
+
+    assert_approximate_f32(-99.0, out[0]);
+    assert_approximate_f32(1.0, out[1]);
+    assert_approximate_f32(51.0, out[2]);
+
+    let normal_map = f64::NormalMap::log2(20.0, 20480.0);
+
+    let mut out = [0.0f64; 2];
+    normal_map.denormalize_array_affine(&[0.5, 1.0], &mut out, 0.5, -10.0);
+
+    // `fast-math` approximates the array path of `Log2Map`, so only compare
+    // against the exact values without it.
+    #[cfg(not(feature = "fast-math"))]
+    {
+        assert_approximate_f64(310.0, out[0]);
+        assert_approximate_f64(10230.0, out[1]);
+    }
+
+    let mut expected = [0.0f64; 2];
+    normal_map.denormalize_array(&[0.5, 1.0], &mut expected);
+    for (value, expected) in out.iter().zip(expected.iter()) {
+        assert_approximate_f64((expected * 0.5) - 10.0, *value);
+    }
+
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic).with_dead_zone(0.2);
+    let mut out = [0.0f32; 2];
+    normal_map.denormalize_array_affine(&[0.45, 1.0], &mut out, 2.0, 1.0);
+    assert_approximate_f32((normal_map.denormalize(0.45) * 2.0) + 1.0, out[0]);
+    assert_approximate_f32(101.0, out[1]);
+}
+
+#[test]
+fn normalizer_denormalizer() {
+    fn apply<F: Fn(f32) -> f32>(f: F, value: f32) -> f32 {
+        f(value)
+    }
+
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    assert_approximate_f32(0.75, apply(normal_map.normalizer(), 25.0));
+    assert_approximate_f32(25.0, apply(normal_map.denormalizer(), 0.75));
+
+    let normal_map = f64::NormalMap::power(0.0, 4.0, 2.0, f64::Unit::Generic);
+    let denormalizer = normal_map.denormalizer();
+    assert_approximate_f64(1.0, denormalizer(0.5));
+    assert_approximate_f64(0.5, normal_map.normalizer()(1.0));
+}
+
+#[test]
+fn iter_samples() {
+    let normal_map = f32::NormalMap::log2(20.0, 20480.0);
+    let samples: Vec<(f32, f32)> = normal_map.iter_samples(3).collect();
+
+    assert_eq!(3, samples.len());
+    assert_eq!((0.0, 20.0), samples[0]);
+    assert_approximate_f32(0.5, samples[1].0);
+    assert_approximate_f32(640.0, samples[1].1);
+    assert_eq!((1.0, 20480.0), samples[2]);
+
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic);
+    let values: Vec<f64> = normal_map
+        .iter_samples(11)
+        .map(|(_, value)| value)
+        .collect();
+
+    assert_eq!(normal_map.ticks(11), values);
+    assert_eq!(0, normal_map.iter_samples(0).count());
+}
+
+#[test]
+fn approx_eq_f64() {
+    let map_f32 = f32::NormalMap::log2(20.0, 20480.0);
+    let map_f64 = f64::NormalMap::log2(20.0, 20480.0);
+
+    assert!(map_f32.approx_eq_f64(&map_f64, 101, 0.01));
+    assert!(!map_f32.approx_eq_f64(&f64::NormalMap::log2(20.0, 20000.0), 101, 0.01));
+
+    let map_f32 = f32::NormalMap::power(0.0, 1.0, 2.0, f32::Unit::Generic);
+    let map_f64 = f64::NormalMap::power(0.0, 1.0, 3.0, f64::Unit::Generic);
+
+    assert!(!map_f32.approx_eq_f64(&map_f64, 11, 1e-3));
+    assert!(map_f32.approx_eq_f64(&map_f64, 2, 1e-6));
+}
+
+#[test]
+fn xy_map() {
+    let pad = f32::XYMap::new(
+        f32::NormalMap::log2(20.0, 20480.0),
+        f32::NormalMap::linear(-1.0, 1.0, f32::Unit::Generic),
+    );
+
+    let (x, y) = pad.normalize_point((640.0, 0.5));
+    assert_approximate_f32(0.5, x);
+    assert_approximate_f32(0.75, y);
+
+    let (x, y) = pad.denormalize_point((1.0, 0.0));
+    assert_approximate_f32(20480.0, x);
+    assert_approximate_f32(-1.0, y);
+
+    let pad = f64::XYMap::new(
+        f64::NormalMap::discrete::<isize>(0, 4),
+        f64::NormalMap::power(0.0, 1.0, 2.0, f64::Unit::Generic),
+    );
+
+    let (x, y) = pad.denormalize_point((0.5, 0.5));
+    assert_approximate_f64(2.0, x);
+    assert_approximate_f64(0.25, y);
+}
+
+#[test]
+fn mapper_conversions() {
+    let power_map = f32::PowerMap::new(0.0, 1.0, 2.0, f32::Unit::Generic);
+    let normal_map = f32::NormalMap::from_mapper(power_map.clone().into());
+
+    assert_eq!(
+        f32::NormalMap::power(0.0, 1.0, 2.0, f32::Unit::Generic),
+        normal_map
+    );
+    assert_eq!(Some(&power_map), normal_map.as_power());
+    assert!(normal_map.as_linear().is_none());
+
+    let normal_map: f64::NormalMap = f64::Mapper::from(f64::Log2Map::new(20.0, 20480.0)).into();
+
+    assert_eq!(
+        Some(20480.0),
+        normal_map.as_log2().map(|mapper| mapper.max())
+    );
+    assert!(normal_map.as_power().is_none());
+}
+
+#[test]
+fn low_resolution_regions() {
+    let normal_map = f32::NormalMap::linear(0.0, 1.0e9, f32::Unit::Generic);
+    assert_eq!(vec![(0.0, 1.0)], normal_map.low_resolution_regions(1.0));
+    assert!(f32::NormalMap::IDENTITY
+        .low_resolution_regions(1.0e-6)
+        .is_empty());
+
+    let normal_map = f32::NormalMap::log2(20.0, 20480.0);
+    let regions = normal_map.low_resolution_regions(0.005);
+
+    assert_eq!(1, regions.len());
+    assert_approximate_f32(0.82, regions[0].0);
+    assert_approximate_f32(1.0, regions[0].1);
+
+    let normal_map = f64::NormalMap::power(0.0, 1.0e17, 2.0, f64::Unit::Generic);
+    let regions = normal_map.low_resolution_regions(22.0);
+
+    assert_eq!(1, regions.len());
+    assert_approximate_f64(0.5, regions[0].0);
+    assert_approximate_f64(1.0, regions[0].1);
+}
+
+#[test]
+fn snap_to() {
+    let normal_map = f32::NormalMap::linear(-1.0, 1.0, f32::Unit::Generic);
+
+    assert_eq!(0.5, normal_map.snap_to(0.52, 0.0, 0.03));
+    assert_eq!(0.52, normal_map.snap_to(0.52, 0.0, 0.01));
+
+    let normal_map = f64::NormalMap::fader_db(6.0, -90.0);
+    let unity = normal_map.normalize(1.0);
+
+    assert_eq!(unity, normal_map.snap_to(unity - 0.01, 1.0, 0.02));
+    assert_eq!(0.1, normal_map.snap_to(0.1, 1.0, 0.02));
+}
+
+#[test]
+fn is_decibels() {
+    assert!(f32::NormalMap::fader_db(6.0, -90.0).is_decibels());
+    assert!(f32::NormalMap::mixer_fader().is_decibels());
+    assert!(!f32::NormalMap::log2_frequency(20.0, 20480.0).is_decibels());
+    assert!(!f64::NormalMap::linear(-90.0, 6.0, f64::Unit::Generic).is_decibels());
+    assert!(!f64::NormalMap::discrete::<isize>(0, 4).is_decibels());
+}
+
+#[test]
+fn density_histogram() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    for share in normal_map.density_histogram(4) {
+        assert_approximate_f32(0.25, share);
+    }
+
+    let normal_map = f32::NormalMap::log2(20.0, 20480.0);
+    let histogram = normal_map.density_histogram(2);
+
+    assert_approximate_f32(0.900_141, histogram[0]);
+    assert_approximate_f32(0.099_859, histogram[1]);
+
+    let normal_map = f64::NormalMap::discrete_reversed::<isize>(0, 4);
+    let histogram = normal_map.density_histogram(8);
+
+    assert_approximate_f64(1.0, histogram.iter().sum());
+    assert!(f64::NormalMap::IDENTITY.density_histogram(0).is_empty());
+    assert_eq!(
+        vec![1.0, 0.0],
+        f64::NormalMap::linear(1.0, 1.0, f64::Unit::Generic).density_histogram(2)
+    );
+}
+
+#[test]
+fn normal_map_builder() {
+    let normal_map = f32::NormalMapBuilder::new(f32::CurveKind::Log2, 20.0, 20480.0)
+        .unit(f32::Unit::Frequency)
+        .build()
+        .unwrap();
+    assert_eq!(f32::NormalMap::log2_frequency(20.0, 20480.0), normal_map);
+
+    let normal_map = f32::NormalMapBuilder::new(f32::CurveKind::Power, 0.0, 1.0)
+        .exponent(2.0)
+        .build()
+        .unwrap();
+    assert_eq!(
+        f32::NormalMap::power(0.0, 1.0, 2.0, f32::Unit::Generic),
+        normal_map
+    );
+
+    let builder = f64::NormalMapBuilder::new(f64::CurveKind::Log2, 0.0, 20480.0);
+    assert_eq!(Err(BuildError::NonPositiveLog2Range), builder.build());
+
+    let builder =
+        f64::NormalMapBuilder::new(f64::CurveKind::Log2, -90.0, 6.0).unit(f64::Unit::Decibels {
+            neg_infinity_clamp: None,
+        });
+    assert_eq!(Err(BuildError::DecibelsOnLog2), builder.build());
+
+    let builder =
+        f64::NormalMapBuilder::new(f64::CurveKind::Linear, -60.0, 6.0).unit(f64::Unit::Decibels {
+            neg_infinity_clamp: Some(-50.0),
+        });
+    assert_eq!(Err(BuildError::ClampAboveMin), builder.build());
+
+    let builder = f64::NormalMapBuilder::new(f64::CurveKind::Linear, 1.0, 0.0);
+    assert_eq!(Err(BuildError::MinAboveMax), builder.build());
+
+    let builder = f64::NormalMapBuilder::new(f64::CurveKind::Power, 0.0, 1.0);
+    assert_eq!(Err(BuildError::MissingExponent), builder.build());
+    assert_eq!(
+        Err(BuildError::InvalidExponent),
+        builder.exponent(-1.0).build()
+    );
+
+    let builder = f64::NormalMapBuilder::new(f64::CurveKind::Linear, 0.0, 1.0).exponent(2.0);
+    assert_eq!(Err(BuildError::UnexpectedExponent), builder.build());
+
+    let builder = f64::NormalMapBuilder::new(f64::CurveKind::Discrete, 0.0, 4.0);
+    assert_eq!(Err(BuildError::UnsupportedKind), builder.build());
+}
+
+#[test]
+fn transfer_curve() {
+    let normal_map = f32::NormalMap::power(0.0, 1.0, 2.0, f32::Unit::Generic);
+    let ys = normal_map.transfer_curve(5);
+
+    assert_eq!(normal_map.ticks(5), ys);
+    assert_approximate_f32(0.0625, ys[1]);
+    assert_approximate_f32(0.25, ys[2]);
+
+    assert!(f64::NormalMap::IDENTITY.transfer_curve(0).is_empty());
+}
+
+#[test]
+fn ticks() {
+    let normal_map = f32::NormalMap::log2(20.0, 20480.0);
+    let ticks = normal_map.ticks(3);
+
+    assert_eq!(3, ticks.len());
+    assert_approximate_f32(20.0, ticks[0]);
+    assert_approximate_f32(640.0, ticks[1]);
+    assert_approximate_f32(20480.0, ticks[2]);
+
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic);
+
+    assert!(normal_map.ticks(0).is_empty());
+    assert_eq!(vec![-50.0], normal_map.ticks(1));
+    assert_eq!(vec![-50.0, 0.0, 50.0], normal_map.ticks(3));
+}
+
+#[test]
+fn effective_resolution() {
+    let normal_map = f32::NormalMap::linear(0.0, 1.0, f32::Unit::Generic);
+    assert_eq!(1000, normal_map.effective_resolution(1000));
+
+    let normal_map = f32::NormalMap::linear(1.0e7, 1.0e7 + 1.0, f32::Unit::Generic);
+    assert_eq!(2, normal_map.effective_resolution(1000));
+
+    let normal_map = f64::NormalMap::linear(1.0e7, 1.0e7 + 1.0, f64::Unit::Generic);
+    assert_eq!(1000, normal_map.effective_resolution(1000));
+
+    let normal_map = f32::NormalMap::discrete(0isize, 4isize);
+    assert_eq!(5, normal_map.effective_resolution(100));
+}
+
+#[test]
+fn max_value_error_vs() {
+    let a = f32::NormalMap::linear(0.0, 1.0, f32::Unit::Generic);
+    let b = f32::NormalMap::power(0.0, 1.0, 2.0, f32::Unit::Generic);
+
+    assert_eq!(0.0, a.max_value_error_vs(&a, 16));
+    assert_approximate_f32(0.25, a.max_value_error_vs(&b, 3));
+
+    let a = f64::NormalMap::linear(0.0, 10.0, f64::Unit::Generic);
+    let b = f64::NormalMap::linear(0.0, 12.0, f64::Unit::Generic);
+    assert_approximate_f64(2.0, a.max_value_error_vs(&b, 5));
+    assert_eq!(0.0, a.max_value_error_vs(&b, 0));
+}
+
+#[test]
+fn is_inverse_of() {
+    let a = f32::NormalMap::log2_frequency(20.0, 20480.0);
+    let b = f32::NormalMap::from_descriptor("log2:20:20480:hz").unwrap();
+    let c = f32::NormalMap::log2_frequency(20.0, 20000.0);
+
+    assert!(a.is_inverse_of(&b, 64, 0.0001));
+    assert!(!a.is_inverse_of(&c, 64, 0.0001));
+    assert!(!f32::NormalMap::discrete(0isize, 4isize).is_inverse_of(
+        &f32::NormalMap::discrete(0isize, 4isize),
+        64,
+        0.0001
+    ));
+
+    let a = f64::NormalMap::power(0.0, 1.0, 2.0, f64::Unit::Generic);
+    let b = f64::NormalMap::power(0.0, 1.0, 3.0, f64::Unit::Generic);
+    assert!(a.is_inverse_of(&a, 64, 1e-12));
+    assert!(!a.is_inverse_of(&b, 64, 1e-12));
+}
+
+#[test]
+fn typed_normal_map() {
+    use crate::f32::marker;
+
+    let db_map = f32::NormalMap::linear(
+        -90.0,
+        6.0,
+        f32::Unit::Decibels {
+            neg_infinity_clamp: None,
+        },
+    );
+    let typed: f32::TypedNormalMap<marker::Decibels> =
+        f32::TypedNormalMap::from_dynamic(db_map.clone()).unwrap();
+    assert_eq!(db_map.normalize(0.5), typed.normalize(0.5));
+    assert_eq!(&db_map, typed.as_dynamic());
+
+    assert!(f32::TypedNormalMap::<marker::Generic>::from_dynamic(typed.into_dynamic()).is_none());
+    assert!(f32::TypedNormalMap::<marker::Frequency>::from_dynamic(db_map).is_none());
+
+    let typed = f64::TypedNormalMap::<f64::marker::Generic>::from_dynamic(
+        f64::NormalMap::discrete(0isize, 4isize),
+    )
+    .unwrap();
+    assert_approximate_f64(2.0, typed.denormalize(0.5));
+    assert_eq!(
+        f64::NormalMap::discrete(0isize, 4isize),
+        f64::NormalMap::from(typed)
+    );
+}
+
+#[test]
+fn adaptive_samples() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    let points = normal_map.adaptive_samples(0.01, 100);
+    assert_eq!(5, points.len());
+    assert_eq!((0.0, -50.0), points[0]);
+    assert_eq!((1.0, 50.0), points[4]);
+
+    let normal_map = f32::NormalMap::power(0.0, 1.0, 4.0, f32::Unit::Generic);
+    let points = normal_map.adaptive_samples(0.001, 1000);
+    assert!(points.len() > 5 && points.len() < 1000);
+    assert!(points.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    for i in 0..=100 {
+        let normalized = i as f32 / 100.0;
+        let segment = points
+            .windows(2)
+            .find(|pair| pair[1].0 >= normalized)
+            .unwrap();
+        let t = (normalized - segment[0].0) / (segment[1].0 - segment[0].0);
+        let chord = segment[0].1 + (t * (segment[1].1 - segment[0].1));
+        assert!((normal_map.denormalize(normalized) - chord).abs() < 0.01);
+    }
+
+    let normal_map = f64::NormalMap::log2_frequency(20.0, 20480.0);
+    assert_eq!(8, normal_map.adaptive_samples(0.0, 8).len());
+    assert_eq!(2, normal_map.adaptive_samples(0.0, 2).len());
+}
+
+#[test]
+fn nice_tick_normals() {
+    let normal_map = f32::NormalMap::log2(20.0, 20000.0);
+
+    let normals = normal_map.nice_tick_normals(f32::TickSpacing::Decade);
+    assert_eq!(3, normals.len());
+    assert_approximate_f32(normal_map.normalize(100.0), normals[0]);
+    assert_approximate_f32(normal_map.normalize(1000.0), normals[1]);
+    assert_approximate_f32(normal_map.normalize(10000.0), normals[2]);
+
+    let normals = normal_map.nice_tick_normals(f32::TickSpacing::OneTwoFive);
+    assert_eq!(10, normals.len());
+    assert_approximate_f32(0.0, normals[0]);
+    assert_approximate_f32(normal_map.normalize(50.0), normals[1]);
+
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic);
+    let normals = normal_map.nice_tick_normals(f64::TickSpacing::OneTwoFive);
+    assert_eq!(11, normals.len());
+    assert_approximate_f64(0.0, normals[0]);
+    assert_approximate_f64(0.1, normals[1]);
+    assert_approximate_f64(1.0, normals[10]);
+
+    let normal_map = f64::NormalMap::linear(0.0, 300.0, f64::Unit::Generic);
+    assert_eq!(
+        7,
+        normal_map
+            .nice_tick_normals(f64::TickSpacing::OneTwoFive)
+            .len()
+    );
+    let normals = normal_map.nice_tick_normals(f64::TickSpacing::Decade);
+    assert_eq!(4, normals.len());
+    assert_approximate_f64(1.0 / 3.0, normals[1]);
+}
+
+#[test]
+fn denormalize_lut() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    let lut = normal_map.build_denormalize_lut(3);
+    assert_approximate_f32(-25.0, f32::NormalMap::lut_denormalize(&lut, 0.25));
+    assert_approximate_f32(50.0, f32::NormalMap::lut_denormalize(&lut, 1.5));
+
+    let normal_map = f64::NormalMap::power(0.0, 1.0, 2.0, f64::Unit::Generic);
+    let lut = normal_map.build_denormalize_lut(257);
+    for i in 0..=100 {
+        let normalized = i as f64 / 100.0;
+        let value = f64::NormalMap::lut_denormalize(&lut, normalized);
+        assert!((value - normal_map.denormalize(normalized)).abs() < 1e-5);
+    }
+    assert_eq!(1.0, f64::NormalMap::lut_denormalize(&lut, 1.0));
+}
+
+#[test]
+fn normalize_in_range() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    assert_approximate_f32(0.25, normal_map.normalize_in_range(2.5, 0.0, 10.0));
+    assert_approximate_f32(0.5, normal_map.normalize_in_range(5.0, 0.0, 10.0));
+    assert_approximate_f32(0.75, normal_map.normalize(25.0));
+
+    let normal_map = f64::NormalMap::power(0.0, 100.0, 2.0, f64::Unit::Generic);
+    let zoomed = f64::NormalMap::power(10.0, 20.0, 2.0, f64::Unit::Generic);
+    assert_approximate_f64(
+        zoomed.normalize(12.5),
+        normal_map.normalize_in_range(12.5, 10.0, 20.0),
+    );
+
+    let normal_map = f64::NormalMap::log2(20.0, 20480.0);
+    assert_approximate_f64(0.5, normal_map.normalize_in_range(200.0, 100.0, 400.0));
+}
+
+#[test]
+fn windowed() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+
+    assert_approximate_f32(0.1, normal_map.normalize_windowed(-50.0, (0.1, 0.9)));
+    assert_approximate_f32(0.5, normal_map.normalize_windowed(0.0, (0.1, 0.9)));
+    assert_approximate_f32(0.9, normal_map.normalize_windowed(60.0, (0.1, 0.9)));
+
+    assert_approximate_f32(-50.0, normal_map.denormalize_windowed(0.0, (0.1, 0.9)));
+    assert_approximate_f32(0.0, normal_map.denormalize_windowed(0.5, (0.1, 0.9)));
+    assert_approximate_f32(50.0, normal_map.denormalize_windowed(0.9, (0.1, 0.9)));
+
+    let normal_map = f64::NormalMap::linear(0.0, 1.0, f64::Unit::Generic);
+    assert_approximate_f64(0.75, normal_map.normalize_windowed(0.5, (0.5, 1.0)));
+    assert_approximate_f64(0.5, normal_map.denormalize_windowed(0.75, (0.5, 1.0)));
+}
+
+#[test]
+#[should_panic]
+fn windowed_invalid_window() {
+    f32::NormalMap::IDENTITY.normalize_windowed(0.5, (0.5, 0.5));
+}
+
+#[test]
+fn nudge() {
+    let normal_map = f32::NormalMap::log2_frequency(20.0, 20480.0);
+
+    assert_approximate_f32(0.55, normal_map.nudge(0.5, 0.05));
+    assert_approximate_f32(1.0, normal_map.nudge(0.98, 0.05));
+    assert_approximate_f32(0.0, normal_map.nudge(0.02, -0.05));
+
+    assert_approximate_f32(1.0, normal_map.nudge_value(640.0, 0.1) / 1280.0);
+    assert_approximate_f32(20480.0, normal_map.nudge_value(10240.0, 0.5));
+
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic);
+    assert_approximate_f64(-45.0, normal_map.nudge_value(-50.0, 0.05));
+    assert_approximate_f64(-50.0, normal_map.nudge_value(-40.0, -0.5));
+}
+
+#[test]
+fn extend_to_include() {
+    let mut normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    normal_map.extend_to_include(60.0);
+    assert_eq!(1.0, normal_map.normalize(60.0));
+    assert_approximate_f32(-50.0, normal_map.denormalize(0.0));
+    normal_map.extend_to_include(10.0);
+    assert_approximate_f32(60.0, normal_map.denormalize(1.0));
+
+    let mut normal_map = f32::NormalMap::log2_frequency(20.0, 20000.0);
+    normal_map.extend_to_include(10.0);
+    assert_eq!(0.0, normal_map.normalize(10.0));
+    normal_map.extend_to_include(-5.0);
+    assert_approximate_f32(10.0, normal_map.denormalize(0.0));
+
+    let mut normal_map = f64::NormalMap::power(
+        -90.0,
+        0.0,
+        2.0,
+        f64::Unit::Decibels {
+            neg_infinity_clamp: None,
+        },
+    );
+    normal_map.extend_to_include(2.0);
+    assert_eq!(1.0, normal_map.normalize(2.0));
+    assert_approximate_f64(2.0, normal_map.denormalize(1.0));
+
+    let mut normal_map = f64::NormalMap::discrete::<isize>(0, 4);
+    normal_map.extend_to_include(-2.0);
+    assert_eq!(0.0, normal_map.normalize(-2.0));
+}
+
+#[test]
+fn ascii_plot() {
+    let normal_map = f32::NormalMap::linear(0.0, 4.0, f32::Unit::Generic);
+    assert_eq!(
+        "    *\n   * \n  *  \n *   \n*    \n",
+        normal_map.ascii_plot(5, 5)
+    );
+
+    let normal_map = f64::NormalMap::power(0.0, 1.0, 2.0, f64::Unit::Generic);
+    let plot = normal_map.ascii_plot(20, 8);
+    assert_eq!(8, plot.lines().count());
+    assert!(plot.lines().all(|line| line.chars().count() == 20));
+    assert_eq!(20, plot.matches('*').count());
+
+    let normal_map = f64::NormalMap::linear(5.0, 5.0, f64::Unit::Generic);
+    assert_eq!("   \n***\n", normal_map.ascii_plot(3, 2));
+}
+
+#[test]
+fn union() {
+    let a = f32::NormalMap::log2_frequency(20.0, 2000.0);
+    let b = f32::NormalMap::log2_frequency(100.0, 20000.0);
+    let map = a.union(&b);
+    assert_eq!(f32::CurveKind::Log2, map.describe().kind);
+    assert_approximate_f32(20.0, map.denormalize(0.0));
+    assert_approximate_f32(1.0, map.denormalize(1.0) / 20000.0);
+
+    let a = f64::NormalMap::power(0.0, 1.0, 2.0, f64::Unit::Generic);
+    let b = f64::NormalMap::linear(-1.0, 0.5, f64::Unit::Generic);
+    let map = a.union(&b);
+    assert_eq!(f64::CurveKind::Linear, map.describe().kind);
+    assert_approximate_f64(-1.0, map.denormalize(0.0));
+    assert_approximate_f64(1.0, map.denormalize(1.0));
+
+    let a = f64::NormalMap::power(0.0, 1.0, 2.0, f64::Unit::Generic);
+    let b = f64::NormalMap::power(0.0, 4.0, 3.0, f64::Unit::Generic);
+    assert_eq!(f64::CurveKind::Linear, a.union(&b).describe().kind);
+}
+
+#[test]
+fn reflect() {
+    let normal_map = f32::NormalMap::log2_frequency(20.0, 20480.0);
+
+    assert_approximate_f32(0.8, normal_map.reflect(0.2));
+    assert_approximate_f32(0.1, normal_map.reflect(0.9));
+    assert_approximate_f32(1.0, normal_map.reflect_value(40.0) / 10240.0);
+    assert_approximate_f32(1.0, normal_map.reflect_value(640.0) / 640.0);
+
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic);
+    assert_approximate_f64(30.0, normal_map.reflect_value(-30.0));
+}
+
+#[test]
+fn normalized_delta_for() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    assert_approximate_f32(0.01, normal_map.normalized_delta_for(0.5, 1.0));
+    assert_approximate_f32(-0.05, normal_map.normalized_delta_for(0.0, -5.0));
+
+    let normal_map = f64::NormalMap::log2_frequency(20.0, 20480.0);
+    let expected = 1.0 / (640.0 * 10.0 * std::f64::consts::LN_2);
+    assert!((normal_map.normalized_delta_for(0.5, 1.0) / expected - 1.0).abs() < 1e-6);
+
+    let normal_map = f32::NormalMap::discrete(0, 4);
+    assert_eq!(0.0, normal_map.normalized_delta_for(0.1, 1.0));
+}
+
+#[test]
+fn resolution_at() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    assert!((normal_map.resolution_at(0.3) / 100.0 - 1.0).abs() < 0.01);
+
+    let power_map = f64::NormalMap::power(20.0, 20000.0, 2.0, f64::Unit::Generic);
+    let log2_map = f64::NormalMap::log2(20.0, 20000.0);
+    assert!(log2_map.resolution_at(0.1) < power_map.resolution_at(0.1));
+    assert!(log2_map.resolution_at(0.9) > power_map.resolution_at(0.9));
+
+    let expected = 640.0 * 10.0 * std::f64::consts::LN_2;
+    let log2_map = f64::NormalMap::log2(20.0, 20480.0);
+    assert!((log2_map.resolution_at(0.5) / expected - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn normalize_quantized() {
+    let normal_map = f32::NormalMap::linear(0.0, 10.0, f32::Unit::Generic);
+    assert_eq!(
+        normal_map.normalize(1.23),
+        normal_map.normalize_quantized(1.230_000_1, 2)
+    );
+    assert_eq!(
+        normal_map.normalize(1.23),
+        normal_map.normalize_quantized(1.229_999_9, 2)
+    );
+    assert_approximate_f32(0.1, normal_map.normalize_quantized(1.04, 0));
+
+    let normal_map = f64::NormalMap::log2_frequency(20.0, 20480.0);
+    assert_eq!(
+        normal_map.normalize(1200.0),
+        normal_map.normalize_quantized(1234.0, 1)
+    );
+
+    let normal_map = f64::NormalMap::linear(
+        -90.0,
+        6.0,
+        f64::Unit::Decibels {
+            neg_infinity_clamp: None,
+        },
+    );
+    assert_approximate_f64(
+        normal_map.db_to_normalized(-6.0),
+        normal_map.normalize_quantized(0.5, 0),
+    );
+
+    let normal_map = f32::NormalMap::discrete::<isize>(0, 4);
+    assert_approximate_f32(0.75, normal_map.normalize_quantized(2.6, 2));
+}
+
+#[test]
+fn clamp_db_and_coeff() {
+    let linear_map = f32::LinearMap::new(
+        -90.0,
+        6.0,
+        f32::Unit::Decibels {
+            neg_infinity_clamp: Some(-60.0),
+        },
+    );
+    assert_eq!(Some(-60.0), linear_map.clamp_db());
+    assert_approximate_f32(0.001, linear_map.clamp_coeff().unwrap());
+
+    let power_map = f64::PowerMap::new(
+        -90.0,
+        6.0,
+        2.0,
+        f64::Unit::Decibels {
+            neg_infinity_clamp: Some(-20.0),
+        },
+    );
+    assert_eq!(Some(-20.0), power_map.clamp_db());
+    assert_approximate_f64(0.1, power_map.clamp_coeff().unwrap());
+
+    let linear_map = f32::LinearMap::new(
+        -90.0,
+        6.0,
+        f32::Unit::Decibels {
+            neg_infinity_clamp: None,
+        },
+    );
+    assert_eq!(None, linear_map.clamp_db());
+    assert_eq!(None, linear_map.clamp_coeff());
+    assert_eq!(
+        None,
+        f64::PowerMap::new(0.0, 1.0, 2.0, f64::Unit::Generic).clamp_coeff()
+    );
+}
+
+#[test]
+fn log2_geometric_center() {
+    let map = f32::Log2Map::new_frequency(20.0, 20480.0);
+    assert_approximate_f32(640.0, map.geometric_center());
+    assert_eq!(map.denormalize(0.5), map.geometric_center());
+
+    let map = f64::Log2Map::new(110.0, 990.0);
+    assert_approximate_f64(330.0, map.geometric_center());
+    assert_approximate_f64((110.0f64 * 990.0).sqrt(), map.geometric_center());
+    assert_eq!(map.denormalize(0.5), map.geometric_center());
+}
+
+#[test]
+fn range_and_octave_span() {
+    assert_approximate_f32(
+        100.0,
+        f32::LinearMap::new(-50.0, 50.0, f32::Unit::Generic).range(),
+    );
+    assert_approximate_f32(
+        2.0,
+        f32::PowerMap::new(-1.0, 1.0, 2.0, f32::Unit::Generic).range(),
+    );
+    assert_approximate_f32(10.0, f32::DiscreteMap::new(-5isize, 5isize).range());
+
+    let log2_map = f32::Log2Map::new_frequency(20.0, 20480.0);
+    assert_approximate_f32(20460.0, log2_map.range());
+    assert_approximate_f32(10.0, log2_map.octave_span());
+
+    assert_approximate_f64(
+        96.0,
+        f64::LinearMap::new(-90.0, 6.0, f64::Unit::Generic).range(),
+    );
+    assert_approximate_f64(3.0, f64::Log2Map::new(110.0, 880.0).octave_span());
+}
+
+#[test]
+fn u14() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+
+    assert_eq!(0, normal_map.normalize_to_u14(-50.0));
+    assert_eq!(0, normal_map.normalize_to_u14(-60.0));
+    assert_eq!(8192, normal_map.normalize_to_u14(0.0));
+    assert_eq!(16383, normal_map.normalize_to_u14(50.0));
+    assert_eq!(16383, normal_map.normalize_to_u14(60.0));
+
+    assert_approximate_f32(-50.0, normal_map.denormalize_from_u14(0));
+    assert_approximate_f32(50.0, normal_map.denormalize_from_u14(16383));
+    assert_approximate_f32(50.0, normal_map.denormalize_from_u14(u16::MAX));
+
+    let normal_map = f64::NormalMap::log2_frequency(20.0, 20480.0);
+    for value in [0u16, 1, 4096, 12345, 16383].iter() {
+        let round_trip = normal_map.normalize_to_u14(normal_map.denormalize_from_u14(*value));
+        assert_eq!(*value, round_trip);
+    }
+}
+
+#[test]
+fn denormalize_with_trim_db() {
+    let normal_map = f32::NormalMap::fader_db(6.0, -90.0);
+
+    let value = normal_map.denormalize(0.75);
+    assert_approximate_f32(value, normal_map.denormalize_with_trim_db(0.75, 0.0));
+    assert_approximate_f32(
+        value * 0.1,
+        normal_map.denormalize_with_trim_db(0.75, -20.0),
+    );
+    assert_eq!(0.0, normal_map.denormalize_with_trim_db(0.0, 12.0));
+
+    let normal_map = f64::NormalMap::linear(0.0, 10.0, f64::Unit::Generic);
+    assert_approximate_f64(50.0, normal_map.denormalize_with_trim_db(0.5, 20.0));
+}
+
+#[test]
+fn amp_and_db_to_normalized() {
+    let db = f32::Unit::Decibels {
+        neg_infinity_clamp: Some(-90.0),
+    };
+    let normal_map = f32::NormalMap::linear(-90.0, 6.0, db);
+
+    assert_approximate_f32(0.9375, normal_map.db_to_normalized(0.0));
+    assert_approximate_f32(0.9375, normal_map.amp_to_normalized(1.0));
+    assert_approximate_f32(0.0, normal_map.db_to_normalized(-120.0));
+    assert_approximate_f32(
+        normal_map.normalize(0.5),
+        f32::LinearMap::new(-90.0, 6.0, db).amp_to_normalized(0.5),
+    );
+
+    let power_map = f64::PowerMap::new(
+        -90.0,
+        6.0,
+        2.0,
+        f64::Unit::Decibels {
+            neg_infinity_clamp: None,
+        },
+    );
+    assert_approximate_f64(power_map.normalize(0.1), power_map.db_to_normalized(-20.0));
+}
+
+#[test]
+#[should_panic(expected = "db_to_normalized requires a map with `Unit::Decibels`")]
+fn db_to_normalized_generic_unit() {
+    f32::NormalMap::linear(-90.0, 6.0, f32::Unit::Generic).db_to_normalized(0.0);
+}
+
+#[test]
+fn denormalize_ftz() {
+    let normal_map = f32::NormalMap::linear(
+        -800.0,
+        0.0,
+        f32::Unit::Decibels {
+            neg_infinity_clamp: None,
+        },
+    );
+
+    assert!(normal_map.denormalize(0.0).is_subnormal());
+    assert_eq!(0.0, normal_map.denormalize_ftz(0.0));
+    assert_approximate_f32(1.0, normal_map.denormalize_ftz(1.0));
+
+    let mut out = [1.0; 3];
+    normal_map.denormalize_array_ftz(&[0.0, 0.5, 1.0], &mut out);
+    assert_eq!(0.0, out[0]);
+    assert!(out[1].is_normal());
+    assert_approximate_f32(1.0, out[2]);
+
+    let normal_map = f64::NormalMap::linear(0.0, 1.0, f64::Unit::Generic);
+    assert_eq!(1e-310, normal_map.denormalize(1e-310));
+    assert_eq!(0.0, normal_map.denormalize_ftz(1e-310));
+}
+
+#[test]
+fn decibels_clamped() {
+    assert_eq!(
+        Ok(f32::Unit::Decibels {
+            neg_infinity_clamp: Some(-90.0)
+        }),
+        f32::Unit::decibels_clamped(-90.0, -90.0)
+    );
+    assert_eq!(
+        Err(crate::ClampAboveMin),
+        f32::Unit::decibels_clamped(-80.0, -90.0)
+    );
+    assert!(f64::Unit::decibels_clamped(-100.0, -90.0).is_ok());
+
+    // A clamp above the minimum silences the bottom of the range.
+    let normal_map = f32::NormalMap::linear(
+        -90.0,
+        6.0,
+        f32::Unit::Decibels {
+            neg_infinity_clamp: Some(-42.0),
+        },
+    );
+    assert_eq!(0.0, normal_map.denormalize(0.0));
+    assert_eq!(0.0, normal_map.denormalize(0.25));
+    assert!(normal_map.denormalize(0.75) > 0.0);
+}
+
+#[test]
+fn quantize_normalized() {
+    let normal_map = f32::NormalMap::power(0.0, 1.0, 0.5, f32::Unit::Generic);
+
+    assert_approximate_f32(0.0, normal_map.quantize_normalized(0.1, 3));
+    assert_approximate_f32(0.5, normal_map.quantize_normalized(0.6, 3));
+    assert_approximate_f32(1.0, normal_map.quantize_normalized(0.9, 3));
+    assert_approximate_f32(0.0, normal_map.quantize_normalized(0.7, 1));
+
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic);
+    let mut out = [0.0; 3];
+    normal_map.quantize_normalized_array(&[-40.0, 10.0, 30.0], &mut out, 5);
+    assert_approximate_f64(0.0, out[0]);
+    assert_approximate_f64(0.5, out[1]);
+    assert_approximate_f64(0.75, out[2]);
+}
+
+#[test]
+fn normalize_array_counting() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+    let mut out = [0.0; 4];
+    let clamped = normal_map.normalize_array_counting(&[-60.0, -50.0, 25.0, 60.0], &mut out);
+    assert_eq!(2, clamped);
+    assert_approximate_f32(0.0, out[0]);
+    assert_approximate_f32(0.75, out[2]);
+
+    let normal_map = f64::NormalMap::log2(20.0, 20480.0);
+    let mut out = [0.0; 2];
+    assert_eq!(
+        1,
+        normal_map.normalize_array_counting(&[10.0, 20.0, 1.0], &mut out)
+    );
+
+    let normal_map = f32::NormalMap::discrete(0isize, 4isize);
+    let mut out = [0.0; 3];
+    assert_eq!(
+        2,
+        normal_map.normalize_array_counting(&[-1.0, 2.0, 5.0], &mut out)
+    );
+}
+
+#[test]
+fn array_n() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+
+    let mut out = [0.0f32; 2];
+    assert_eq!(
+        2,
+        normal_map.normalize_array_n(&[0.0, 25.0, 50.0], &mut out)
+    );
+    assert_eq!([0.5, 0.75], out);
+
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic);
+
+    let mut out = [0.0f64; 3];
+    assert_eq!(1, normal_map.denormalize_array_n(&[0.25], &mut out));
+    assert_eq!([-25.0, 0.0, 0.0], out);
+}
+
+#[test]
+fn denormalize_fill() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+
+    let mut out = [0.0f32; 4];
+    normal_map.denormalize_fill(0.75, &mut out);
+    assert_eq!([25.0; 4], out);
+
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic);
+
+    let mut out = [0.0f64; 4];
+    normal_map.denormalize_fill(0.25, &mut out);
+    assert_eq!([-25.0; 4], out);
+}
+
+#[test]
+fn interpolate_values() {
+    let normal_map = f32::NormalMap::log2_frequency(20.0, 20480.0);
+    assert_approximate_f32(
+        1.0,
+        normal_map.interpolate_values(100.0, 400.0, 0.5) / 200.0,
+    );
+    assert_approximate_f32(
+        1.0,
+        normal_map.interpolate_values(100.0, 400.0, 0.0) / 100.0,
+    );
+    assert_approximate_f32(
+        1.0,
+        normal_map.interpolate_values(100.0, 400.0, 1.0) / 400.0,
+    );
+
+    let normal_map = f64::NormalMap::log2_frequency(20.0, 20480.0);
+    let mut out = [0.0; 3];
+    normal_map.interpolate_values_array(1000.0, 8000.0, &[0.0, 1.0 / 3.0, 1.0], &mut out);
+    assert_approximate_f64(1.0, out[0] / 1000.0);
+    assert_approximate_f64(1.0, out[1] / 2000.0);
+    assert_approximate_f64(1.0, out[2] / 8000.0);
+}
+
+#[test]
+fn lerp_denormalize() {
+    let a = f32::NormalMap::linear(20.0, 20480.0, f32::Unit::Generic);
+    let b = f32::NormalMap::log2(20.0, 20480.0);
+
+    assert_approximate_f32(10250.0, f32::NormalMap::lerp_denormalize(&a, &b, 0.5, 0.0));
+    assert_approximate_f32(640.0, f32::NormalMap::lerp_denormalize(&a, &b, 0.5, 1.0));
+    assert_approximate_f32(5445.0, f32::NormalMap::lerp_denormalize(&a, &b, 0.5, 0.5));
+
+    let a = f64::NormalMap::linear(0.0, 100.0, f64::Unit::Generic);
+    let b = f64::NormalMap::power(0.0, 100.0, 2.0, f64::Unit::Generic);
+
+    let mut out = [0.0f64; 3];
+    f64::NormalMap::lerp_denormalize_array(&a, &b, &[0.0, 0.5, 1.0], &mut out, 0.25);
+
+    assert_approximate_f64(0.0, out[0]);
+    assert_approximate_f64(43.75, out[1]);
+    assert_approximate_f64(100.0, out[2]);
+}
+
+#[test]
+fn try_array_length_mismatch() {
+    let normal_map = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+
+    let mut out = [0.0f32; 2];
+    assert_eq!(
+        Ok(()),
+        normal_map.try_normalize_array(&[-50.0, 50.0], &mut out)
+    );
+    assert_eq!(
+        Err(LengthMismatch {
+            input_len: 3,
+            output_len: 2
+        }),
+        normal_map.try_normalize_array(&[0.0, 0.0, 0.0], &mut out)
+    );
+    assert_eq!(
+        Err(LengthMismatch {
+            input_len: 1,
+            output_len: 2
+        }),
+        normal_map.try_denormalize_array(&[0.0], &mut out)
+    );
+}
+
+#[cfg(feature = "fast-math")]
+#[test]
+fn log2_fast_math() {
+    let map = f32::Log2Map::new(20.0, 20480.0);
+
+    let normalized: Vec<f32> = (0..=1000).map(|i| i as f32 / 1000.0).collect();
+    let mut values = vec![0.0; normalized.len()];
+    let mut round_trip = vec![0.0; normalized.len()];
+    map.denormalize_array(&normalized, &mut values);
+    map.normalize_array(&values, &mut round_trip);
+
+    for i in 0..normalized.len() {
+        let precise = map.denormalize(normalized[i]);
+        assert!((values[i] - precise).abs() <= precise * 1e-5);
+        assert!((round_trip[i] - normalized[i]).abs() <= 1e-4);
+    }
+
+    let map = f64::Log2Map::new(0.5, 8.0);
+    let mut out = [0.0; 3];
+    map.normalize_array(&[0.25, 2.0, 16.0], &mut out);
+    assert!((out[1] - 0.5).abs() <= 1e-4);
+    assert_eq!(0.0, out[0]);
+    assert_eq!(1.0, out[2]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn array_par() {
+    let normal_map = f32::NormalMap::log2_frequency(20.0, 20480.0);
+
+    let normalized: Vec<_> = (0..100_000).map(|i| i as f32 / 99_999.0).collect();
+    let mut values = vec![0.0; 100_000];
+    let mut values_par = vec![0.0; 100_001];
+    normal_map.denormalize_array(&normalized, &mut values);
+    normal_map.denormalize_array_par(&normalized, &mut values_par);
+    assert_eq!(values[..], values_par[..100_000]);
+    assert_eq!(0.0, values_par[100_000]);
+
+    let mut out = vec![0.0; 100_000];
+    let mut out_par = vec![0.0; 100_000];
+    normal_map.normalize_array(&values, &mut out);
+    normal_map.normalize_array_par(&values, &mut out_par);
+    assert_eq!(out, out_par);
+
+    let normal_map = f64::NormalMap::linear(-50.0, 50.0, f64::Unit::Generic);
+    let mut out = [0.0; 3];
+    normal_map.normalize_array_par(&[-50.0, 0.0, 50.0], &mut out);
+    assert_eq!([0.0, 0.5, 1.0], out);
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn half_round_trip() {
+    use half::f16;
+
+    let normal_map = crate::f16::NormalMap::from(f32::NormalMap::log2(20.0, 20480.0));
+
+    for i in 0..=100 {
+        let normalized = f16::from_f32(i as f32 / 100.0);
+        let value = normal_map.denormalize(normalized);
+        let round_trip = normal_map.normalize(value);
+        assert!((round_trip.to_f32() - normalized.to_f32()).abs() <= 0.002);
+    }
+
+    let normal_map =
+        crate::f16::NormalMap::new(f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic));
+    let mut out = [f16::ZERO; 2];
+    normal_map.normalize_array(&[f16::from_f32(-25.0), f16::from_f32(60.0)], &mut out);
+    assert_eq!([f16::from_f32(0.25), f16::ONE], out);
+}
+
+#[cfg(feature = "fixed")]
+#[test]
+fn fixed_point() {
+    use ::fixed::types::I16F16;
+
+    let normal_map = crate::fixed::NormalMap::linear(I16F16::from_num(-50), I16F16::from_num(50));
+
+    assert_eq!(
+        I16F16::from_num(0.75),
+        normal_map.normalize(I16F16::from_num(25))
+    );
+    assert_eq!(I16F16::ONE, normal_map.normalize(I16F16::from_num(60)));
+    assert_eq!(
+        I16F16::from_num(-25),
+        normal_map.denormalize(I16F16::from_num(0.25))
+    );
+    assert_eq!(I16F16::from_num(50), normal_map.denormalize(I16F16::ONE));
+
+    let normal_map = crate::fixed::NormalMap::discrete(-5, 5);
+    let mut out = [I16F16::ZERO; 3];
+    normal_map.denormalize_array(
+        &[I16F16::ZERO, I16F16::from_num(0.76), I16F16::ONE],
+        &mut out,
+    );
+
+    assert_eq!(
+        [
+            I16F16::from_num(-5),
+            I16F16::from_num(3),
+            I16F16::from_num(5)
+        ],
+        out
+    );
+    assert!(
+        (normal_map.normalize(I16F16::from_num(2.6)) - I16F16::from_num(0.8)).abs()
+            <= I16F16::DELTA
+    );
+
+    let discrete_map = crate::fixed::DiscreteMap::new(0, 4);
+    assert_eq!(2, discrete_map.denormalize_int(I16F16::from_num(0.5)));
+}
+
+#[cfg(feature = "approx")]
+#[test]
+fn approx_eq() {
+    use approx::{assert_abs_diff_eq, assert_abs_diff_ne, assert_relative_eq};
+
+    let a = f32::NormalMap::power(-50.0, 50.0, 0.5, f32::Unit::Generic);
+    let b = f32::NormalMap::power(-50.00001, 50.0, 0.5, f32::Unit::Generic);
+    let c = f32::NormalMap::linear(-50.0, 50.0, f32::Unit::Generic);
+
+    assert_abs_diff_eq!(a, b, epsilon = 0.0001);
+    assert_abs_diff_ne!(a, b);
+    assert_abs_diff_ne!(a, c, epsilon = 1.0);
+    assert_relative_eq!(a, b, max_relative = 0.001);
+
+    let a = f64::NormalMap::linear(
+        -90.0,
+        6.0,
+        f64::Unit::Decibels {
+            neg_infinity_clamp: Some(-90.0),
+        },
+    );
+    let b = f64::NormalMap::linear(
+        -90.0,
+        6.0,
+        f64::Unit::Decibels {
+            neg_infinity_clamp: Some(-90.0000001),
+        },
+    );
+
+    assert_abs_diff_eq!(a, b, epsilon = 0.001);
+    assert_abs_diff_ne!(a, b);
+}
+
 fn assert_approximate_f32(a: f32, b: f32) {
     assert!(
         (a - b).abs() <= 0.0001,